@@ -0,0 +1,104 @@
+use crate::*;
+use std::num::FpCategory;
+
+/// Category of a float, like [`FpCategory`] with extra categories for non-canonical encodings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Category {
+    /// Not a number.
+    Nan,
+
+    /// Positive or negative infinity.
+    Infinite,
+
+    /// Positive or negative zero.
+    Zero,
+
+    /// Non-zero with an all zero exponent, in a format with subnormals.
+    Subnormal,
+
+    /// Any other finite value.
+    Normal,
+
+    /// A zero with the sign bit set, in a format without negative zero.
+    NonCanonicalZero,
+
+    /// A finite value with the all one exponent reserved for infinities, never the result of rounding.
+    NonCanonicalFinite,
+}
+
+impl From<Category> for FpCategory {
+    /// Get the category of the value, non-canonical encodings are categorized by their value.
+    fn from(category: Category) -> FpCategory {
+        match category {
+            Category::Nan => FpCategory::Nan,
+            Category::Infinite => FpCategory::Infinite,
+            Category::Zero | Category::NonCanonicalZero => FpCategory::Zero,
+            Category::Subnormal => FpCategory::Subnormal,
+            Category::Normal | Category::NonCanonicalFinite => FpCategory::Normal,
+        }
+    }
+}
+
+impl Float {
+    /// Get the category of the float with the special values of the format, see [`Float::category`].
+    pub fn classify(&self) -> FpCategory {
+        self.category().into()
+    }
+
+    /// Get the category of the float, including the non-canonical encodings.
+    pub fn category(&self) -> Category {
+        match self.value() {
+            Value::NaN { .. } => Category::Nan,
+            Value::Infinity { .. } => Category::Infinite,
+            Value::Zero { .. } if self.is_sign_negative() && !self.format.specials.negative_zero => {
+                Category::NonCanonicalZero
+            },
+            Value::Zero { .. } => Category::Zero,
+            Value::Finite(_) if round::grid_index(self) > round::grid_max(&self.format) => {
+                Category::NonCanonicalFinite
+            },
+            Value::Finite(_) if round::is_subnormal(self) => Category::Subnormal,
+            Value::Finite(_) => Category::Normal,
+        }
+    }
+
+    /// Check if the float is NaN.
+    pub fn is_nan(&self) -> bool {
+        self.classify() == FpCategory::Nan
+    }
+
+    /// Check if the float is positive or negative infinity.
+    pub fn is_infinite(&self) -> bool {
+        self.classify() == FpCategory::Infinite
+    }
+
+    /// Check if the float is neither infinite nor NaN.
+    pub fn is_finite(&self) -> bool {
+        !self.is_nan() && !self.is_infinite()
+    }
+
+    /// Check if the float is neither zero, infinite, subnormal nor NaN.
+    pub fn is_normal(&self) -> bool {
+        self.classify() == FpCategory::Normal
+    }
+
+    /// Check if the float is subnormal.
+    pub fn is_subnormal(&self) -> bool {
+        self.classify() == FpCategory::Subnormal
+    }
+
+    /// Check if the float is a canonical encoding of its value.
+    pub fn is_canonical(&self) -> bool {
+        !matches!(self.category(), Category::NonCanonicalZero | Category::NonCanonicalFinite)
+    }
+
+    /// Check if the sign bit is set, including zeros and NaN.
+    pub fn is_sign_negative(&self) -> bool {
+        self.format.signed && self.bits[0]
+    }
+
+    /// Check if the sign bit is not set, including zeros and NaN.
+    pub fn is_sign_positive(&self) -> bool {
+        !self.is_sign_negative()
+    }
+}
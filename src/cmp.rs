@@ -0,0 +1,98 @@
+use crate::*;
+use fraction::prelude::*;
+use std::cmp::Ordering;
+
+/// A float compared by its value with IEEE 754 semantics,
+/// i.e. positive and negative zero are equal, and NaN is unordered and not equal to anything.
+///
+/// [`Float`] itself is compared by its format and bit pattern.
+#[derive(Debug, Clone)]
+pub struct Numeric(pub Float);
+
+impl PartialEq for Numeric {
+    fn eq(&self, other: &Numeric) -> bool {
+        self.0.cmp_value(&other.0) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for Numeric {
+    fn partial_cmp(&self, other: &Numeric) -> Option<Ordering> {
+        self.0.cmp_value(&other.0)
+    }
+}
+
+impl From<Float> for Numeric {
+    fn from(float: Float) -> Numeric {
+        Numeric(float)
+    }
+}
+
+impl Float {
+    /// Compare with `other` by the IEEE 754 total order.
+    ///
+    /// The order is negative NaN, negative infinity, negative finite values, negative zero, positive zero,
+    /// positive finite values, positive infinity, and positive NaN,
+    /// where the sign is the sign bit, and NaN are ordered by the quiet bit and then the payload.
+    /// Different encodings of the same value are ordered by their bit patterns.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The float to compare with.
+    pub fn total_cmp(&self, other: &Float) -> Ordering {
+        match (self.is_sign_negative(), other.is_sign_negative()) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => self.total_cmp_magnitude(other),
+            (true, true) => other.total_cmp_magnitude(self),
+        }
+    }
+
+    /// Compare with `other` by the exact values, none if either is NaN.
+    /// The formats may differ, and positive and negative zero are equal.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The float to compare with, may be of a different format.
+    pub fn cmp_value(&self, other: &Float) -> Option<Ordering> {
+        // infinities are ranked beside all the finite values
+        let key = |float: &Float| match float.decode() {
+            round::Decoded::Finite { negative: true, value } => Some((0, -value)),
+            round::Decoded::Finite { negative: false, value } => Some((0, value)),
+            round::Decoded::Infinity { negative } => Some((if negative { -1 } else { 1 }, BigFraction::from(0u32))),
+            round::Decoded::NaN { .. } => None,
+        };
+
+        let ((a_rank, a_value), (b_rank, b_value)) = (key(self)?, key(other)?);
+        Some(a_rank.cmp(&b_rank).then_with(|| a_value.partial_cmp(&b_value).unwrap()))
+    }
+
+    /// Check if `other` encodes the same number by the exact values, e.g. to check that a conversion is exact.
+    /// The formats may differ, positive and negative zero are the same, and NaN is the same as any NaN.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The float to compare with, may be of a different format.
+    pub fn equals_value(&self, other: &Float) -> bool {
+        match self.cmp_value(other) {
+            Some(order) => order == Ordering::Equal,
+            None => self.is_nan() && other.is_nan(),
+        }
+    }
+
+    /// Compare the magnitude of the total order with `other`, ignoring the sign bit.
+    fn total_cmp_magnitude(&self, other: &Float) -> Ordering {
+        let key = |float: &Float| match float.value() {
+            Value::Finite(value) => (0, value.abs(), round::grid_index(float)),
+            Value::Zero { .. } => (0, BigFraction::from(0u32), round::grid_index(float)),
+            Value::Infinity { .. } => (1, BigFraction::from(0u32), BigUint::from(0u32)),
+            Value::NaN { quiet, payload } => (2 + quiet as u8, BigFraction::from(0u32), payload),
+        };
+
+        let ((a_rank, a_value, a_index), (b_rank, b_value, b_index)) = (key(self), key(other));
+
+        a_rank
+            .cmp(&b_rank)
+            .then_with(|| a_value.partial_cmp(&b_value).unwrap())
+            .then_with(|| a_index.cmp(&b_index))
+    }
+}
@@ -90,6 +90,11 @@ impl Components {
         self.sign.is_some() as usize + self.exp.len() + self.mant.len()
     }
 
+    /// Check if the components have no bits.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
     /// Get the sign bit as a str of ether '1' or '0'.
     pub fn get_sign(&self) -> Option<&str> {
         self.sign.as_ref().map(|s| if *s { "1" } else { "0" })
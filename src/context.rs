@@ -0,0 +1,263 @@
+use crate::*;
+use std::cell::RefCell;
+
+/// Sticky IEEE exception flags, raised by operations and kept until cleared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Flags {
+    /// The rounded result differs from the exact result.
+    pub inexact: bool,
+
+    /// The result is tiny and inexact.
+    pub underflow: bool,
+
+    /// The result exceeds the largest finite value of the format after rounding.
+    pub overflow: bool,
+
+    /// An infinite result is produced from finite operands, e.g. division of a non-zero by zero.
+    pub divide_by_zero: bool,
+
+    /// The operation has no meaningful result, e.g. the square root of a negative number.
+    pub invalid: bool,
+}
+
+impl Flags {
+    /// Check if none of the flags are raised.
+    pub fn is_empty(&self) -> bool {
+        *self == Flags::default()
+    }
+}
+
+/// When a result is detected to be tiny, i.e. below the smallest normal magnitude of the format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Tininess {
+    /// Tiny if the result rounded as if the exponent range were unbounded is below the smallest normal magnitude.
+    #[default]
+    AfterRounding,
+
+    /// Tiny if the exact result is below the smallest normal magnitude.
+    BeforeRounding,
+}
+
+/// Result of a finite value that overflows the format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum OverflowPolicy {
+    /// Round to infinity or the largest finite value by the rounding mode, as in IEEE 754.
+    /// An error if rounded to infinity and the format has no infinity.
+    #[default]
+    Infinity,
+
+    /// Saturate to the largest finite value, like the saturating conversions of OCP FP8.
+    /// Infinite results are kept, unless the format has no infinity.
+    Saturate,
+
+    /// Round like [`OverflowPolicy::Infinity`], but NaN if rounded to infinity and the format has no infinity,
+    /// like the non-saturating conversions of OCP FP8.
+    NaN,
+
+    /// An error.
+    Error,
+}
+
+/// Result of a non-zero value that rounds to zero in the format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum UnderflowPolicy {
+    /// Flush to zero, as in IEEE 754.
+    /// An error if the format has no zero.
+    #[default]
+    Zero,
+
+    /// The smallest positive magnitude of the format with the same sign,
+    /// i.e. the smallest subnormal if the format has subnormals.
+    /// This is also the result of zero if the format has no zero.
+    MinSubnormal,
+
+    /// An error.
+    Error,
+}
+
+/// Behavior of NaN results, which differs between hardware.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NanProfile {
+    /// Whether the sign and payload of a NaN operand propagate to the result, quieted.
+    /// Otherwise, a NaN result is always the default NaN.
+    pub propagate: bool,
+
+    /// Which NaN operand propagates if there are more than one.
+    pub priority: NanPriority,
+
+    /// The NaN produced by invalid operations, and by operations on NaN if they do not propagate.
+    pub default_nan: DefaultNan,
+}
+
+impl NanProfile {
+    /// Propagate the first NaN operand, with the canonical quiet NaN as default.
+    pub fn ieee() -> NanProfile {
+        NanProfile {
+            propagate: true,
+            priority: NanPriority::First,
+            default_nan: DefaultNan::Canonical,
+        }
+    }
+
+    /// x86 SSE and AVX, propagate the first NaN operand, with the negative quiet NaN (real indefinite) as default.
+    pub fn x86_sse() -> NanProfile {
+        NanProfile {
+            propagate: true,
+            priority: NanPriority::First,
+            default_nan: DefaultNan::NegativeCanonical,
+        }
+    }
+
+    /// ARM with default NaN mode disabled, propagate the first signaling NaN operand before quiet ones.
+    pub fn arm() -> NanProfile {
+        NanProfile {
+            propagate: true,
+            priority: NanPriority::SignalingFirst,
+            default_nan: DefaultNan::Canonical,
+        }
+    }
+
+    /// ARM with default NaN mode enabled, always produce the canonical quiet NaN.
+    pub fn arm_default_nan() -> NanProfile {
+        NanProfile {
+            propagate: false,
+            priority: NanPriority::SignalingFirst,
+            default_nan: DefaultNan::Canonical,
+        }
+    }
+
+    /// RISC-V, always produce the canonical quiet NaN.
+    pub fn riscv() -> NanProfile {
+        NanProfile {
+            propagate: false,
+            priority: NanPriority::First,
+            default_nan: DefaultNan::Canonical,
+        }
+    }
+
+    /// NVIDIA PTX, always produce the positive all one NaN, e.g. `0x7fffffff` for binary32.
+    pub fn nvidia_ptx() -> NanProfile {
+        NanProfile {
+            propagate: false,
+            priority: NanPriority::First,
+            default_nan: DefaultNan::AllOnes,
+        }
+    }
+}
+
+impl Default for NanProfile {
+    fn default() -> Self {
+        NanProfile::ieee()
+    }
+}
+
+/// Which NaN operand propagates to the result.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum NanPriority {
+    /// The first NaN operand.
+    #[default]
+    First,
+
+    /// The first signaling NaN operand, or the first quiet NaN operand if there is no signaling one.
+    SignalingFirst,
+}
+
+/// The default NaN of a format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DefaultNan {
+    /// The positive canonical quiet NaN of the format.
+    #[default]
+    Canonical,
+
+    /// The canonical quiet NaN of the format with the sign bit set.
+    NegativeCanonical,
+
+    /// The positive NaN with all the exponent and mantissa bits set, or the canonical one if it is not a NaN.
+    AllOnes,
+}
+
+/// Environment of float operations, carrying the rounding mode and collecting the exception flags.
+/// It also controls the handling of subnormal values, overflow, underflow, NaN results
+/// and the random source of stochastic rounding.
+///
+/// Operations on [`Float`] without an explicit context use the thread-local default context.
+#[derive(derivative::Derivative, Clone)]
+#[derivative(Debug, PartialEq, Eq, Default)]
+pub struct FloatContext {
+    /// The rounding mode used if the result cannot be represented exactly.
+    pub rounding: RoundingMode,
+
+    /// The exception flags raised since they were last cleared.
+    pub flags: Flags,
+
+    /// Whether tiny results are flushed to zero (FTZ), raising the underflow and inexact flags.
+    pub flush_to_zero: bool,
+
+    /// Whether subnormal operands are treated as zero (DAZ).
+    pub denormals_are_zero: bool,
+
+    /// When a result is detected to be tiny, for the underflow flag and flushing to zero.
+    pub tininess: Tininess,
+
+    /// The result of a value that overflows the format.
+    pub overflow: OverflowPolicy,
+
+    /// The result of a non-zero value that rounds to zero,
+    /// tiny results flushed to zero are not affected unless the format has no zero.
+    pub underflow: UnderflowPolicy,
+
+    /// The behavior of NaN results.
+    pub nan: NanProfile,
+
+    /// The source of random bits for stochastic rounding.
+    #[derivative(Debug = "ignore", PartialEq = "ignore", Default(value = "Box::new(SplitMix64::default())"))]
+    pub random: Box<dyn RandomSource>,
+}
+
+thread_local! {
+    static DEFAULT_CONTEXT: RefCell<FloatContext> = RefCell::new(FloatContext::default());
+}
+
+impl FloatContext {
+    /// Create from the given rounding mode, with no flags raised.
+    pub fn new(rounding: RoundingMode) -> FloatContext {
+        FloatContext {
+            rounding,
+            ..Default::default()
+        }
+    }
+
+    /// Clear all the exception flags.
+    pub fn clear_flags(&mut self) {
+        self.flags = Flags::default();
+    }
+
+    /// Call the function with the thread-local default context.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from within another call to this function.
+    pub fn with_default<T>(f: impl FnOnce(&mut FloatContext) -> T) -> T {
+        DEFAULT_CONTEXT.with(|ctx| f(&mut ctx.borrow_mut()))
+    }
+
+    /// Get a copy of the thread-local default context.
+    pub fn get_default() -> FloatContext {
+        Self::with_default(|ctx| ctx.clone())
+    }
+
+    /// Replace the thread-local default context, returning the previous one.
+    pub fn set_default(ctx: FloatContext) -> FloatContext {
+        Self::with_default(|default| std::mem::replace(default, ctx))
+    }
+
+    /// Call the function with the thread-local default context, using the given rounding mode.
+    pub(crate) fn with_default_rounding<T>(rounding: RoundingMode, f: impl FnOnce(&mut FloatContext) -> T) -> T {
+        Self::with_default(|ctx| {
+            let prev = std::mem::replace(&mut ctx.rounding, rounding);
+            let result = f(ctx);
+            ctx.rounding = prev;
+            result
+        })
+    }
+}
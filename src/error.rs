@@ -25,4 +25,16 @@ pub enum Error {
 
     #[error("negative sign for unsigned format")]
     NegativeSign,
+
+    #[error("mismatched formats of the operands")]
+    MismatchedFormat,
+
+    #[error("invalid operation for format without NaN")]
+    InvalidOperation,
+
+    #[error("division by zero for format without infinity")]
+    DivisionByZero,
+
+    #[error("NaN not representable in the format")]
+    UnrepresentableNaN,
 }
\ No newline at end of file
@@ -1,6 +1,6 @@
 use crate::*;
 use bitvec::field::BitField;
-use fraction::{prelude::*, Num, ToPrimitive};
+use fraction::prelude::*;
 use core::str::FromStr;
 
 /// A floating point number, also contains the format information.
@@ -12,117 +12,26 @@ pub struct Float {
 
 impl Float {
     /// Create from the given format and string.
+    /// The number is rounded with the thread-local default context, see [`FloatContext::from_str`].
     /// 
     /// # Arguments
     /// 
     /// * `format` - The format of the number.
     /// * `str` - The number in decimal form.
     pub fn from_str(format: Format, s: &str) -> Result<Self, error::Error> {
-        // sign
-        match s.chars().next() {
-            Some('0'..='9' | '+' | '-') => {},
-            _ => return Err(error::Error::ParseStringError),
-        }
-
-        let sign = s.starts_with('-');
-        let s = if sign { &s[1..] } else { s };
-
-        if sign && !format.signed {
-            return Err(error::Error::NegativeSign);
-        }
-
-        let frac = BigFraction::from_str(s).map_err(|_| error::Error::ParseStringError)?;
-
-        // extract integral and fractional parts
-        let (mut int, mut frac) = (
-            BigUint::from(frac.clone().numer().unwrap() / frac.clone().denom().unwrap()),
-            frac.fract(),
-        );
-
-        let mut int_bits = String::new();
-        let mut frac_bits = String::new();
-
-        let exp = match int > BigUint::from(0u32) {
-            false if frac == BigFraction::from(0u32) => {
-                -(format.excess as i128)
-            },
-            true => {
-                let mut exp = 0i128;
-
-                // get integral part
-                while int > BigUint::from(1u32) {
-                    int_bits.push(if int.clone() % BigUint::from(2u32) == BigUint::from(1u32) { '1' } else { '0' });
-                    int /= 2u32;
-                    exp += 1;
-                }
-
-                // get fractional part
-                while frac != BigFraction::from(0u32) && int_bits.len() + frac_bits.len() < format.mant {
-                    frac *= BigFraction::from(2u32);
-                    frac_bits.insert(0, if frac > BigFraction::from(1u32) { '1' } else { '0' });
-                    frac %= BigFraction::from(1u32);
-                }
-
-                exp
-            },
-            false => {
-                let mut exp = 0i128;
-
-                // remove leading zeros of fraction
-                loop {
-                    frac *= BigFraction::from(2u32);
-                    exp -= 1;
-
-                    if frac > BigFraction::from(1u32) {
-                        frac %= BigFraction::from(1u32);
-                        break;
-                    }
-                }
-
-                // get fractional part
-                while frac != BigFraction::from(0u32) && frac_bits.len() < format.mant {
-                    frac *= BigFraction::from(2u32);
-                    frac_bits.insert(0, if frac > BigFraction::from(1u32) { '1' } else { '0' });
-
-                    frac %= BigFraction::from(1u32);
-                }
-
-                exp
-            },
-        };
-
-        let len = int_bits.len() + frac_bits.len(); 
-
-        let int_bits = int_bits
-            .chars()
-            .rev()
-            .take(format.mant)
-            .collect::<String>();
-        
-        let frac_bits = frac_bits
-            .chars()
-            .rev()
-            .chain(std::iter::repeat('0').take(if len < format.mant {
-                format.mant - len
-            } else {
-                0
-            }))
-            .collect::<String>();
-
-        if exp < -(format.excess as i128) || exp >= ((1 << format.exp) - format.excess) as i128 {
-            return Err(error::Error::OutOfRange);
-        }
-
-        let exp = exp + format.excess as i128;
+        FloatContext::with_default(|ctx| ctx.from_str(format, s))
+    }
 
-        let signed = format.signed;
-        let width = format.exp;
-        Self::from_fields(
-            format,
-            if signed { Some(sign) } else { None },
-            format!("0b{:0width$b}", exp, width = width as usize).as_str(),
-            ("0b".to_owned() + &int_bits + &frac_bits).as_str(),
-        )
+    /// Create from the given format and string, rounding with the given mode.
+    /// The flags are raised in the thread-local default context.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `format` - The format of the number.
+    /// * `str` - The number in decimal form.
+    /// * `mode` - The rounding mode used if the number cannot be represented exactly.
+    pub fn from_str_with_rounding(format: Format, s: &str, mode: RoundingMode) -> Result<Self, error::Error> {
+        FloatContext::with_default_rounding(mode, |ctx| ctx.from_str(format, s))
     }
 
     /// Create from the given format and bit pattern.
@@ -136,11 +45,11 @@ impl Float {
 
         if bits.len() < format.len() {
             formatted_bits.extend(std::iter::repeat(false).take(format.len() - bits.len()));
-            formatted_bits.extend(bits.into_iter());
+            formatted_bits.extend(bits);
         } else if bits
             .iter()
             .take(bits.len() - format.len())
-            .any(|b| b == true)
+            .any(|b| *b)
         {
             return Err(error::Error::InsufficientBitsForBitPattern);
         } else {
@@ -184,7 +93,7 @@ impl Float {
         } else if exp
             .clone()
             .take((comps_format.exp - format.exp) as usize)
-            .any(|b| b == true)
+            .any(|b| b)
         {
             return Err(error::Error::InsufficientExponentBits);
         } else {
@@ -197,7 +106,7 @@ impl Float {
         } else if mant
             .clone()
             .take(comps_format.mant - format.mant)
-            .any(|b| b == true)
+            .any(|b| b)
         {
             return Err(error::Error::InsufficientMantissaBits);
         } else {
@@ -231,6 +140,29 @@ impl Float {
         )
     }
 
+    /// Create a NaN of the given format.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `format` - The format of the NaN.
+    /// * `quiet` - Whether the NaN is quiet or signaling.
+    /// * `payload` - The payload of the NaN, stored in the mantissa bits other than the quiet bit.
+    pub fn new_nan(format: Format, quiet: bool, payload: BigUint) -> Result<Float, error::Error> {
+        round::nan_with_payload(&format, false, quiet, &payload)
+    }
+
+    /// Convert to the given format, preserving the value.
+    /// The value is rounded with the given mode if it cannot be represented exactly,
+    /// the flags are raised in the thread-local default context.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `format` - The format to convert to.
+    /// * `mode` - The rounding mode used if the value cannot be represented exactly.
+    pub fn convert_to(&self, format: Format, mode: RoundingMode) -> Result<Float, error::Error> {
+        FloatContext::with_default_rounding(mode, |ctx| ctx.convert(self, format))
+    }
+
     /// Get the start indices of exponent and mantissa.
     /// 0th element is the start index of exponent, 1st element is the start index of mantissa.
     pub fn get_start_indices(&self) -> (usize, usize) {
@@ -244,7 +176,7 @@ impl Float {
     pub fn to_comps(&self) -> Components {
         let signed = self.format.signed;
         let exp_range = signed as usize..(signed as usize + self.format.exp as usize);
-        let mant_range = exp_range.end..(exp_range.end + self.format.mant as usize);
+        let mant_range = exp_range.end..(exp_range.end + self.format.mant);
 
         let sign = match signed {
             true => Some(self.bits[0]),
@@ -257,46 +189,109 @@ impl Float {
         Components { sign, exp, mant }
     }
     
+    /// Decode into the value with the semantics of the format.
+    pub fn value(&self) -> Value {
+        self.format.decode(&self.to_comps())
+    }
+
+    /// Get the payload if the float is NaN.
+    pub fn nan_payload(&self) -> Option<BigUint> {
+        match self.value() {
+            Value::NaN { payload, .. } => Some(payload),
+            _ => None,
+        }
+    }
+
+    /// Decode into the sign and the exact magnitude.
+    pub(crate) fn decode(&self) -> round::Decoded {
+        match self.value() {
+            Value::Finite(value) => round::Decoded::Finite {
+                negative: value < BigFraction::from(0u32),
+                value: value.abs(),
+            },
+            Value::Zero { negative } => round::Decoded::Finite { negative, value: BigFraction::from(0u32) },
+            Value::Infinity { negative } => round::Decoded::Infinity { negative },
+            Value::NaN { quiet, payload } => round::Decoded::NaN {
+                negative: self.is_sign_negative(),
+                quiet,
+                payload: Some(payload),
+            },
+        }
+    }
+
     /// Create a `f32` from the given `Float`.
-    /// The result may has a lost of information.
+    /// The result may has a lost of information, the value is rounded to nearest, ties to even.
     pub fn to_f32(&self) -> f32 {
-        let Components { sign, exp, mant } = self.to_comps();
+        match FloatContext::default().convert(self, Format::ieee_binary32()) {
+            Ok(float) => float.to_f32_raw(),
+            Err(_) => f32::NAN,
+        }
+    }
 
-        let exp = i64::from_str_radix(&exp.to_bin_string(), 2).unwrap();
-        let mant = BigUint::from_str_radix(&mant.to_bin_string(), 2).unwrap();
+    /// Create a `f64` from the given `Float`.
+    /// The result may has a lost of information, the value is rounded to nearest, ties to even.
+    pub fn to_f64(&self) -> f64 {
+        match FloatContext::default().convert(self, Format::ieee_binary64()) {
+            Ok(float) => float.to_f64_raw(),
+            Err(_) => f64::NAN,
+        }
+    }
+    
+    /// Create from the given raw encoding of IEEE binary16.
+    pub fn from_ieee_binary16_bits(bits: u16) -> Float {
+        Float::from_bits(Format::ieee_binary16(), BitPattern::from_value(bits)).unwrap()
+    }
 
-        let sign = match sign {
-            Some(true) => -1f32,
-            Some(false) => 1f32,
-            None => 1f32,
-        };
+    /// Create from the given raw encoding of IEEE binary128.
+    pub fn from_ieee_binary128_bits(bits: u128) -> Float {
+        let bits = (0..128).rev().map(|i| (bits >> i) & 1 == 1).collect();
+        Float::from_bits(Format::ieee_binary128(), bits).unwrap()
+    }
 
-        let exp = 2f32.powi((exp - self.format.excess as i64) as i32);
-        let mant = mant.to_f32().unwrap() / num_traits::pow(2f32, self.format.mant) + 1f32;
+    /// Create from the given raw encoding of bfloat16.
+    pub fn from_bfloat16_bits(bits: u16) -> Float {
+        Float::from_bits(Format::bfloat16(), BitPattern::from_value(bits)).unwrap()
+    }
 
-        sign * exp * mant
+    /// Create from the given raw encoding of TensorFloat-32 in the lower 19 bits.
+    ///
+    /// Returns an error if any of the upper 13 bits is set.
+    pub fn from_tf32_bits(bits: u32) -> Result<Float, error::Error> {
+        Float::from_bits(Format::tf32(), BitPattern::from_value(bits))
     }
 
-    /// Create a `f64` from the given `Float`.
-    /// The result may has a lost of information.
-    pub fn to_f64(&self) -> f64 {
-        let Components { sign, exp, mant } = self.to_comps();
+    /// Get the raw encoding of the float converted to IEEE binary16.
+    /// The value is rounded to nearest, ties to even.
+    pub fn to_ieee_binary16_bits(&self) -> u16 {
+        self.to_preset(Format::ieee_binary16()).bits.load_be::<u16>()
+    }
 
-        let exp = i64::from_str_radix(&exp.to_bin_string(), 2).unwrap();
-        let mant = BigUint::from_str_radix(&mant.to_bin_string(), 2).unwrap();
+    /// Get the raw encoding of the float converted to IEEE binary128.
+    /// The value is rounded to nearest, ties to even.
+    pub fn to_ieee_binary128_bits(&self) -> u128 {
+        self.to_preset(Format::ieee_binary128()).bits.iter().fold(0, |acc, b| (acc << 1) | *b as u128)
+    }
 
-        let sign = match sign {
-            Some(true) => -1f64,
-            Some(false) => 1f64,
-            None => 1f64,
-        };
+    /// Get the raw encoding of the float converted to bfloat16.
+    /// The value is rounded to nearest, ties to even.
+    pub fn to_bfloat16_bits(&self) -> u16 {
+        self.to_preset(Format::bfloat16()).bits.load_be::<u16>()
+    }
 
-        let exp = 2f64.powi((exp - self.format.excess as i64) as i32);
-        let mant = mant.to_f64().unwrap() / num_traits::pow(2f64, self.format.mant) + 1f64;
+    /// Get the raw encoding of the float converted to TensorFloat-32, in the lower 19 bits.
+    /// The value is rounded to nearest, ties to even.
+    pub fn to_tf32_bits(&self) -> u32 {
+        self.to_preset(Format::tf32()).bits.load_be::<u32>()
+    }
 
-        sign * exp * mant
+    /// Convert to a format with infinities and NaN, rounding to nearest, ties to even.
+    fn to_preset(&self, format: Format) -> Float {
+        match FloatContext::default().convert(self, format.clone()) {
+            Ok(float) => float,
+            Err(_) => round::nan(&format).unwrap(),
+        }
     }
-    
+
     /// Create a `f32` from the given `Float`.
     /// Raw transmutation from the bit pattern.
     pub fn to_f32_raw(&self) -> f32 {
@@ -310,6 +305,61 @@ impl Float {
     }
 }
 
+impl FloatContext {
+    /// Create a float from the given format and string, rounding with the context.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `format` - The format of the number.
+    /// * `str` - The number in decimal form.
+    pub fn from_str(&mut self, format: Format, s: &str) -> Result<Float, error::Error> {
+        // sign
+        match s.chars().next() {
+            Some('0'..='9' | '+' | '-') => {},
+            _ => return Err(error::Error::ParseStringError),
+        }
+
+        let sign = s.starts_with('-');
+        let s = if sign { &s[1..] } else { s };
+
+        if sign && !format.signed {
+            return Err(error::Error::NegativeSign);
+        }
+
+        let value = BigFraction::from_str(s).map_err(|_| error::Error::ParseStringError)?;
+
+        round::round(&format, sign, &value, self)
+    }
+
+    /// Convert the float to the given format, rounding with the context.
+    /// NaN is converted with the NaN profile of the context.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `float` - The float to convert.
+    /// * `format` - The format to convert to.
+    pub fn convert(&mut self, float: &Float, format: Format) -> Result<Float, error::Error> {
+        match self.decode(float) {
+            round::Decoded::Finite { negative, value } => round::round(&format, negative, &value, self),
+            round::Decoded::Infinity { negative } => {
+                round::infinity_or_max(&format, negative, self).ok_or_else(|| {
+                    self.flags.invalid = true;
+                    error::Error::OutOfRange
+                })
+            },
+            round::Decoded::NaN { negative, payload, .. } => {
+                // the payload is kept in the most significant bits of the mantissa
+                let payload = payload.map(|payload| match format.mant >= float.format.mant {
+                    true => payload << (format.mant - float.format.mant),
+                    false => payload >> (float.format.mant - format.mant),
+                });
+
+                self.nan_result(&format, negative, payload).inspect_err(|_| self.flags.invalid = true)
+            },
+        }
+    }
+}
+
 impl From<f32> for Float {
     /// Create from the given `f32`, using IEEE binary32 format.
     fn from(f: f32) -> Float {
@@ -332,52 +382,27 @@ impl From<f64> for Float {
 
 impl std::fmt::Display for Float {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        let comps = self.to_comps();
-
-        if let Some(s) = (self.format.interpret)(&comps) {
-            return write!(f, "{}", s);
-        }
-
-        // sign
-        let sign = match comps.sign {
-            Some(true) => "-",
-            _ => "",
-        };
-
-        // exp
-        let exp =
-            BigInt::from_str_radix(&comps.exp.to_bin_string(), 2).unwrap() - self.format.excess.clone();
-
-        let exp = match exp < BigInt::from(0i32) {
-            true => BigFraction::new(BigUint::from(1u32), BigUint::from(2u32) << ((-exp).to_usize().unwrap() - 1)),
-            false => BigFraction::from(BigUint::from(1u32) << exp.to_usize().unwrap()),
+        let (sign, value) = match self.value() {
+            Value::Finite(value) if value < BigFraction::from(0u32) => ("-", value.abs()),
+            Value::Finite(value) => ("", value),
+            Value::Zero { negative } => (if negative { "-" } else { "" }, BigFraction::from(0u32)),
+            Value::Infinity { negative: false } => return write!(f, "inf"),
+            Value::Infinity { negative: true } => return write!(f, "-inf"),
+            Value::NaN { quiet: true, .. } => return write!(f, "NaN"),
+            Value::NaN { quiet: false, .. } => return write!(f, "sNaN"),
         };
 
-        // mant
-        let frac = comps.mant
-            .iter()
-            .fold((BigUint::from(0u32), BigUint::from(0u32)),
-                |(numer, denom), b| {
-                    (
-                        numer * 2u32 + if *b { 1u32 } else { 0u32 },
-                        denom * 2u32 + 1u32,
-                    )
-                }
-            );
-        
-        let frac =
-            BigFraction::from(1u32)
-            + BigFraction::new(frac.0, frac.1);
-        
-        let value = frac * exp;
-
         // output, by default 6 significant digits
         if let Some(prec) = f.precision() {
-            return write!(f, "{}{}", sign, format!("{:.1$}", value, prec))
+            return write_decimal(f, sign, &value, prec);
+        }
+
+        if value == BigFraction::from(0u32) {
+            return write!(f, "{}0", sign);
         }
 
         if value > BigFraction::from(9999999u32) {
-            return write!(f, "{}{}", sign, BigUint::from(value.clone().numer().unwrap() / value.clone().denom().unwrap()))
+            return write!(f, "{}{}", sign, value.clone().numer().unwrap() / value.clone().denom().unwrap())
         }
 
         let prec = if value > BigFraction::from(999999u32) {
@@ -407,6 +432,18 @@ impl std::fmt::Display for Float {
             }
         };
 
-        write!(f, "{}{}", sign, format!("{:.1$}", value, prec))
+        write!(f, "{}{:.2$}", sign, value, prec)
+    }
+}
+
+/// Write the non-negative value in decimal with `prec` digits after the point, rounding ties to even.
+fn write_decimal(f: &mut std::fmt::Formatter, sign: &str, value: &BigFraction, prec: usize) -> std::fmt::Result {
+    let scaled = ops::round_ties_even(&(value.clone() * BigFraction::from(num_traits::pow(BigUint::from(10u32), prec))));
+    let digits = format!("{:0>width$}", scaled.numer().unwrap(), width = prec + 1);
+    let (int, frac) = digits.split_at(digits.len() - prec);
+
+    match prec {
+        0 => write!(f, "{}{}", sign, int),
+        _ => write!(f, "{}{}.{}", sign, int, frac),
     }
 }
@@ -1,8 +1,8 @@
 use crate::*;
+use fraction::prelude::*;
 
 /// Format of the float, storing the number of bit for each fields.
-#[derive(derivative::Derivative, Clone)]
-#[derivative(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Format {
     /// Whether the float is signed or not, if true a bit will be assigned for the sign.
     pub signed: bool,
@@ -18,8 +18,12 @@ pub struct Format {
     /// This is the value that is subtracted from the exponent to get the actual exponent.
     pub excess: u32,
 
-    #[derivative(Debug = "ignore", PartialEq = "ignore")]
-    pub interpret: Interpret,
+    /// Whether an all zero exponent encodes subnormal values, i.e. the mantissa has no implicit leading 1.
+    /// Otherwise, the all zero exponent is normal, and only the all zero exponent and mantissa encodes zero.
+    pub subnormal: bool,
+
+    /// The encodings of special values, i.e. infinities, NaN and negative zero.
+    pub specials: Specials,
 }
 
 impl Format {
@@ -34,7 +38,8 @@ impl Format {
             exp,
             mant,
             excess,
-            interpret: |_| None,
+            subnormal: true,
+            specials: Specials::finite(),
         }
     }
 
@@ -49,7 +54,8 @@ impl Format {
             exp,
             mant,
             excess,
-            interpret: |_| None,
+            subnormal: true,
+            specials: Specials::finite(),
         }
     }
 
@@ -64,7 +70,8 @@ impl Format {
             exp,
             mant,
             excess,
-            interpret: |_| None,
+            subnormal: true,
+            specials: Specials::finite(),
         }
     }
 
@@ -80,7 +87,8 @@ impl Format {
             exp,
             mant,
             excess: (1 << (exp - 1)) - 1,
-            interpret: |_| None,
+            subnormal: true,
+            specials: Specials::finite(),
         }
     }
 
@@ -96,7 +104,133 @@ impl Format {
             exp,
             mant,
             excess: (1 << (exp - 1)) - 1,
-            interpret: |_| None,
+            subnormal: true,
+            specials: Specials::finite(),
+        }
+    }
+
+    /// Create the IEEE binary interchange format of `k` bits, with IEEE special values.
+    /// For `k` of at least 128, the exponent is `round(4 * log2(k)) - 13` bits,
+    /// the mantissa is the rest, and the excess is `(1 << (exp - 1)) - 1`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `k` is not 16, 32, 64, or a multiple of 32 of at least 128,
+    /// or if the exponent of the format is larger than 31 bits.
+    ///
+    /// # Arguments
+    ///
+    /// * `k` - The number of bits of the format.
+    pub fn ieee_interchange(k: usize) -> Format {
+        let exp = match k {
+            16 => 5,
+            32 => 8,
+            64 => 11,
+            k if k >= 128 && k % 32 == 0 => (4.0 * (k as f64).log2()).round() as usize - 13,
+            _ => panic!("no IEEE binary interchange format of {} bits", k),
+        };
+
+        if exp > 31 {
+            panic!("exponent bits must be less than 32");
+        }
+
+        Format {
+            specials: Specials::ieee(),
+            ..Format::new_ieee_excess(exp as u8, k - 1 - exp)
+        }
+    }
+
+    /// Create the bfloat16 format, i.e. the upper 16 bits of IEEE binary32.
+    /// The exponent is 8 bits and biased by 127, and the mantissa is 7 bits, with IEEE special values.
+    pub fn bfloat16() -> Format {
+        Format {
+            specials: Specials::ieee(),
+            ..Format::new(8, 7, 127)
+        }
+    }
+
+    /// Create the TensorFloat-32 format, i.e. the upper 19 bits of IEEE binary32.
+    /// The exponent is 8 bits and biased by 127, and the mantissa is 10 bits, with IEEE special values.
+    pub fn tf32() -> Format {
+        Format {
+            specials: Specials::ieee(),
+            ..Format::new(8, 10, 127)
+        }
+    }
+
+    /// Create the OCP FP8 E4M3 format, i.e. E4M3FN.
+    /// The exponent is 4 bits and biased by 7, and the mantissa is 3 bits,
+    /// with no infinities and NaN only at the all one exponent and mantissa.
+    pub fn ocp_fp8_e4m3() -> Format {
+        Format {
+            specials: Specials {
+                infinity: false,
+                nan: NanEncoding::AllOnes,
+                ..Specials::ieee()
+            },
+            ..Format::new(4, 3, 7)
+        }
+    }
+
+    /// Create the OCP FP8 E5M2 format.
+    /// The exponent is 5 bits and biased by 15, and the mantissa is 2 bits, with IEEE special values.
+    pub fn ocp_fp8_e5m2() -> Format {
+        Format {
+            specials: Specials::ieee(),
+            ..Format::new(5, 2, 15)
+        }
+    }
+
+    /// Create the FP8 E4M3FNUZ format.
+    /// The exponent is 4 bits and biased by 8, and the mantissa is 3 bits,
+    /// with no infinities or negative zero, and NaN only at the negative zero pattern.
+    pub fn fp8_e4m3fnuz() -> Format {
+        Format {
+            specials: Specials::fnuz(),
+            ..Format::new(4, 3, 8)
+        }
+    }
+
+    /// Create the FP8 E5M2FNUZ format.
+    /// The exponent is 5 bits and biased by 16, and the mantissa is 2 bits,
+    /// with no infinities or negative zero, and NaN only at the negative zero pattern.
+    pub fn fp8_e5m2fnuz() -> Format {
+        Format {
+            specials: Specials::fnuz(),
+            ..Format::new(5, 2, 16)
+        }
+    }
+
+    /// Create the OCP MX FP6 E2M3 element format.
+    /// The exponent is 2 bits and biased by 1, and the mantissa is 3 bits, with no infinities or NaN.
+    pub fn ocp_mx_fp6_e2m3() -> Format {
+        Format::new(2, 3, 1)
+    }
+
+    /// Create the OCP MX FP6 E3M2 element format.
+    /// The exponent is 3 bits and biased by 3, and the mantissa is 2 bits, with no infinities or NaN.
+    pub fn ocp_mx_fp6_e3m2() -> Format {
+        Format::new(3, 2, 3)
+    }
+
+    /// Create the OCP MX FP4 E2M1 element format.
+    /// The exponent is 2 bits and biased by 1, and the mantissa is 1 bit, with no infinities or NaN.
+    pub fn ocp_mx_fp4_e2m1() -> Format {
+        Format::new(2, 1, 1)
+    }
+
+    /// Create the OCP MX E8M0 scale format, i.e. an unsigned power of two.
+    /// The exponent is 8 bits and biased by 127, and there is no mantissa,
+    /// with no zero or subnormals, and NaN only at the all one pattern.
+    pub fn ocp_mx_e8m0() -> Format {
+        Format {
+            subnormal: false,
+            specials: Specials {
+                nan: NanEncoding::AllOnes,
+                zero: false,
+                ..Specials::finite()
+            },
+            ..Format::new_unsigned(8, 0, 127)
         }
     }
 
@@ -104,13 +238,70 @@ impl Format {
     pub fn len(&self) -> usize {
         self.signed as usize + self.exp as usize + self.mant
     }
+
+    /// Check if the format has no bits.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Decode for Format {
+    /// Decode the components with the special value encodings of the format.
+    fn decode(&self, comps: &Components) -> Value {
+        let negative = comps.sign == Some(true);
+        let exp_all_one = self.exp > 0 && comps.exp.is_all_one();
+        let mant_all_zero = comps.mant.is_all_zero();
+
+        match self.specials.nan {
+            NanEncoding::AllOnesExponent if exp_all_one && !mant_all_zero => {
+                let mut mant = comps.mant.iter().by_vals();
+
+                return Value::NaN {
+                    quiet: mant.next().unwrap_or(false) != (self.specials.quiet_bit == QuietBit::Legacy),
+                    payload: mant.fold(BigUint::from(0u32), |acc, b| acc * 2u32 + b as u32),
+                };
+            },
+            NanEncoding::AllOnes if self.exp as usize + self.mant > 0
+                && comps.exp.is_all_one()
+                && comps.mant.is_all_one() => {
+                return Value::NaN { quiet: true, payload: BigUint::from(0u32) };
+            },
+            NanEncoding::NegativeZero if negative && comps.exp.is_all_zero() && mant_all_zero => {
+                return Value::NaN { quiet: true, payload: BigUint::from(0u32) };
+            },
+            _ => {},
+        }
+
+        if self.specials.infinity && exp_all_one && mant_all_zero {
+            return Value::Infinity { negative };
+        }
+
+        let k = comps.exp
+            .iter()
+            .chain(comps.mant.iter())
+            .fold(BigUint::from(0u32), |acc, b| acc * 2u32 + *b as u32);
+
+        match round::grid_value(self, &k) {
+            value if value == BigFraction::from(0u32) => Value::Zero { negative: negative && self.specials.negative_zero },
+            value if negative => Value::Finite(-value),
+            value => Value::Finite(value),
+        }
+    }
 }
 
 impl IeeeBinary for Format {
+    /// The exponent is 5 bits and biased by 15, and the mantissa is 10 bits.
+    fn ieee_binary16() -> Self {
+        Self {
+            specials: Specials::ieee(),
+            ..Self::new(5, 10, 15)
+        }
+    }
+
     /// The exponent is 8 bits and biased by 127, and the mantissa is 23 bits.
     fn ieee_binary32() -> Self {
         Self {
-            interpret: Interpret::ieee_binary32(),
+            specials: Specials::ieee(),
             ..Self::new(8, 23, 127)
         }
     }
@@ -118,8 +309,113 @@ impl IeeeBinary for Format {
     /// The exponent is 11 bits and biased by 1023, and the mantissa is 52 bits.
     fn ieee_binary64() -> Self {
         Self {
-            interpret: Interpret::ieee_binary64(),
+            specials: Specials::ieee(),
             ..Self::new(11, 52, 1023)
         }
     }
+
+    /// The exponent is 15 bits and biased by 16383, and the mantissa is 112 bits.
+    fn ieee_binary128() -> Self {
+        Self::ieee_interchange(128)
+    }
+
+    /// The exponent is 19 bits and biased by 262143, and the mantissa is 236 bits.
+    fn ieee_binary256() -> Self {
+        Self::ieee_interchange(256)
+    }
+}
+
+/// Encodings of the special values of a format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Specials {
+    /// Whether the all one exponent with a zero mantissa encodes infinities.
+    /// If so, the other patterns with the all one exponent are never the result of rounding.
+    pub infinity: bool,
+
+    /// Which bit patterns encode NaN.
+    pub nan: NanEncoding,
+
+    /// How the most significant bit of the mantissa distinguishes quiet and signaling NaN,
+    /// for NaN with the all one exponent.
+    pub quiet_bit: QuietBit,
+
+    /// Whether the sign of zero is kept, otherwise the negative zero pattern is decoded as positive zero,
+    /// unless it encodes NaN.
+    pub negative_zero: bool,
+
+    /// Whether the all zero exponent and mantissa encodes zero,
+    /// otherwise it is the smallest value of a format without subnormals, like the E8M0 scale.
+    pub zero: bool,
+}
+
+impl Specials {
+    /// Every bit pattern is a finite value, with signed zeros.
+    pub fn finite() -> Specials {
+        Specials {
+            infinity: false,
+            nan: NanEncoding::None,
+            quiet_bit: QuietBit::Ieee2008,
+            negative_zero: true,
+            zero: true,
+        }
+    }
+
+    /// Infinities and NaN with the all one exponent, with signed zeros, as in IEEE 754.
+    pub fn ieee() -> Specials {
+        Specials {
+            infinity: true,
+            nan: NanEncoding::AllOnesExponent,
+            quiet_bit: QuietBit::Ieee2008,
+            negative_zero: true,
+            zero: true,
+        }
+    }
+
+    /// NaN only at the negative zero pattern, with no infinities or negative zero, as in the FNUZ formats.
+    pub fn fnuz() -> Specials {
+        Specials {
+            infinity: false,
+            nan: NanEncoding::NegativeZero,
+            quiet_bit: QuietBit::Ieee2008,
+            negative_zero: false,
+            zero: true,
+        }
+    }
+}
+
+impl Default for Specials {
+    fn default() -> Self {
+        Specials::finite()
+    }
+}
+
+/// Bit patterns that encode NaN.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum NanEncoding {
+    /// There is no NaN.
+    #[default]
+    None,
+
+    /// The all one exponent with a non-zero mantissa, as in IEEE 754.
+    /// The most significant bit of the mantissa is the quiet bit, the remaining bits are the payload.
+    AllOnesExponent,
+
+    /// Only the all one exponent and mantissa, of either sign.
+    AllOnes,
+
+    /// Only the negative zero pattern, so there is no negative zero.
+    NegativeZero,
+}
+
+/// Convention of the quiet bit, i.e. the most significant bit of the mantissa of NaN.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum QuietBit {
+    /// The quiet bit is set for quiet NaN, as in IEEE 754-2008.
+    /// The canonical quiet NaN has a zero payload.
+    #[default]
+    Ieee2008,
+
+    /// The quiet bit is set for signaling NaN, as in legacy MIPS and PA-RISC.
+    /// The canonical quiet NaN has an all one payload.
+    Legacy,
 }
@@ -27,14 +27,41 @@
 //! ```
 
 mod format;
-pub use format::Format;
+pub use format::{Format, NanEncoding, QuietBit, Specials};
 
 mod float;
 pub use float::Float;
 
+mod value;
+pub use value::{Decode, Value};
+
+mod classify;
+pub use classify::Category;
+
+mod cmp;
+pub use cmp::Numeric;
+
+mod sign;
+
+mod next;
+pub use next::FloatRange;
+
+mod context;
+pub use context::{
+    DefaultNan, Flags, FloatContext, NanPriority, NanProfile, OverflowPolicy, Tininess, UnderflowPolicy,
+};
+
+mod random;
+pub use random::{RandomSource, RandomSourceClone, SplitMix64};
+
 mod components;
 pub use components::Components;
 
+mod ops;
+
+mod round;
+pub use round::RoundingMode;
+
 mod utils;
 pub use utils::*;
 
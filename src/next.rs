@@ -0,0 +1,130 @@
+use crate::*;
+use fraction::{prelude::*, ToPrimitive};
+use std::cmp::Ordering;
+
+/// Iterator over the consecutive values of a format in ascending order, see [`Float::range`].
+#[derive(Debug, Clone)]
+pub struct FloatRange {
+    next: Option<Float>,
+    end: Float,
+}
+
+impl Iterator for FloatRange {
+    type Item = Float;
+
+    fn next(&mut self) -> Option<Float> {
+        let current = self.next.take()?;
+
+        match current.cmp_value(&self.end)? {
+            Ordering::Greater => None,
+            Ordering::Equal => Some(current),
+            Ordering::Less => {
+                self.next = current.next_up().ok();
+                Some(current)
+            },
+        }
+    }
+}
+
+impl Float {
+    /// Get the smallest value of the format that is greater than the float.
+    /// The next value of negative zero is the smallest positive value, and of the largest finite value is infinity.
+    /// NaN is canonicalized with the thread-local default context.
+    ///
+    /// Returns an error if the float is the largest finite value and the format has no infinity.
+    pub fn next_up(&self) -> Result<Float, error::Error> {
+        self.step(true)
+    }
+
+    /// Get the largest value of the format that is less than the float.
+    /// The previous value of positive zero is the negative smallest positive value,
+    /// and of the negative largest finite value is negative infinity.
+    /// NaN is canonicalized with the thread-local default context.
+    ///
+    /// Returns an error if there is no such value in the format, e.g. for zero in an unsigned format.
+    pub fn next_down(&self) -> Result<Float, error::Error> {
+        self.step(false)
+    }
+
+    /// Get the unit in the last place of the float, i.e. the distance between consecutive values of its exponent.
+    /// The unit of zero is the smallest positive subnormal, the unit of infinity is positive infinity.
+    /// NaN is canonicalized with the thread-local default context.
+    pub fn ulp(&self) -> Result<Float, error::Error> {
+        let format = &self.format;
+
+        match self.decode() {
+            round::Decoded::NaN { .. } => FloatContext::with_default(|ctx| ctx.canonicalize(self)),
+            round::Decoded::Infinity { .. } => round::infinity(format, false).ok_or(error::Error::OutOfRange),
+            round::Decoded::Finite { .. } => {
+                let k = round::grid_index(self).min(round::grid_max(format));
+                let exp = (k >> format.mant).to_i64().unwrap();
+                let exp = if format.subnormal { exp.max(1) } else { exp };
+                let unit = round::pow2(exp - format.excess as i64 - format.mant as i64);
+
+                round::round(format, false, &unit, &mut FloatContext::default())
+            },
+        }
+    }
+
+    /// Create an iterator over the consecutive values from `start` to `end` inclusively, in ascending order.
+    /// Zeros are visited once, and special encodings are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The first value.
+    /// * `end` - The last value, must be of the same format.
+    pub fn range(start: &Float, end: &Float) -> Result<FloatRange, error::Error> {
+        if start.format != end.format {
+            return Err(error::Error::MismatchedFormat);
+        }
+
+        if start.is_nan() || end.is_nan() {
+            return Err(error::Error::InvalidOperation);
+        }
+
+        Ok(FloatRange {
+            next: Some(start.clone()),
+            end: end.clone(),
+        })
+    }
+
+    /// Get the neighbouring value of the float, upward or downward.
+    fn step(&self, up: bool) -> Result<Float, error::Error> {
+        let format = &self.format;
+        let max = round::grid_max(format);
+
+        match self.decode() {
+            round::Decoded::NaN { .. } => FloatContext::with_default(|ctx| ctx.canonicalize(self)),
+            round::Decoded::Infinity { negative } if negative != up => Ok(self.clone()),
+            round::Decoded::Infinity { negative } => Ok(round::encode(format, negative, &max)),
+            round::Decoded::Finite { negative, value } => {
+                let k = round::grid_index(self).min(&max + 1u32);
+                let zero = value == BigFraction::from(0u32);
+
+                // away from zero if moving in the direction of the sign, zero always moves away
+                if zero || negative != up {
+                    let negative = if zero { !up } else { negative };
+
+                    if negative && !format.signed {
+                        return Err(error::Error::NegativeSign);
+                    }
+
+                    return match k + 1u32 {
+                        k if k > max => round::infinity(format, negative).ok_or(error::Error::OutOfRange),
+                        k => Ok(round::encode(format, negative, &k)),
+                    };
+                }
+
+                // the smallest value of a format without zero is next to its negation
+                if k == BigUint::from(0u32) {
+                    return match format.signed {
+                        true => Ok(round::encode(format, !negative, &k)),
+                        false => Err(error::Error::OutOfRange),
+                    };
+                }
+
+                Ok(round::encode(format, negative, &(k - 1u32)))
+            },
+        }
+    }
+}
@@ -0,0 +1,788 @@
+use crate::*;
+use round::Decoded;
+use fraction::prelude::*;
+
+impl Float {
+    /// Add `rhs` to the float with the thread-local default context, see [`FloatContext::add`].
+    ///
+    /// # Arguments
+    ///
+    /// * `rhs` - The float to add, must be of the same format.
+    pub fn add(&self, rhs: &Float) -> Result<Float, error::Error> {
+        FloatContext::with_default(|ctx| ctx.add(self, rhs))
+    }
+
+    /// Subtract `rhs` from the float with the thread-local default context, see [`FloatContext::sub`].
+    ///
+    /// # Arguments
+    ///
+    /// * `rhs` - The float to subtract, must be of the same format.
+    pub fn sub(&self, rhs: &Float) -> Result<Float, error::Error> {
+        FloatContext::with_default(|ctx| ctx.sub(self, rhs))
+    }
+
+    /// Multiply the float by `rhs` with the thread-local default context, see [`FloatContext::mul`].
+    ///
+    /// # Arguments
+    ///
+    /// * `rhs` - The float to multiply by, must be of the same format.
+    pub fn mul(&self, rhs: &Float) -> Result<Float, error::Error> {
+        FloatContext::with_default(|ctx| ctx.mul(self, rhs))
+    }
+
+    /// Compute `self * a + b` with a single rounding with the thread-local default context,
+    /// see [`FloatContext::mul_add`].
+    ///
+    /// # Arguments
+    ///
+    /// * `a` - The float to multiply by, must be of the same format.
+    /// * `b` - The float to add to the product, must be of the same format.
+    pub fn mul_add(&self, a: &Float, b: &Float) -> Result<Float, error::Error> {
+        FloatContext::with_default(|ctx| ctx.mul_add(self, a, b))
+    }
+
+    /// Divide the float by `rhs` with the thread-local default context, see [`FloatContext::div`].
+    ///
+    /// # Arguments
+    ///
+    /// * `rhs` - The float to divide by, must be of the same format.
+    pub fn div(&self, rhs: &Float) -> Result<Float, error::Error> {
+        FloatContext::with_default(|ctx| ctx.div(self, rhs))
+    }
+
+    /// Get the square root of the float with the thread-local default context, see [`FloatContext::sqrt`].
+    pub fn sqrt(&self) -> Result<Float, error::Error> {
+        FloatContext::with_default(|ctx| ctx.sqrt(self))
+    }
+
+    /// Get the reciprocal of the square root of the float with the thread-local default context,
+    /// see [`FloatContext::rsqrt`].
+    pub fn rsqrt(&self) -> Result<Float, error::Error> {
+        FloatContext::with_default(|ctx| ctx.rsqrt(self))
+    }
+
+    /// Round the float to an integral value toward negative infinity.
+    pub fn floor(&self) -> Result<Float, error::Error> {
+        FloatContext::with_default(|ctx| ctx.floor(self))
+    }
+
+    /// Round the float to an integral value toward positive infinity.
+    pub fn ceil(&self) -> Result<Float, error::Error> {
+        FloatContext::with_default(|ctx| ctx.ceil(self))
+    }
+
+    /// Round the float to an integral value toward zero.
+    pub fn trunc(&self) -> Result<Float, error::Error> {
+        FloatContext::with_default(|ctx| ctx.trunc(self))
+    }
+
+    /// Round the float to the nearest integral value, ties away from zero.
+    pub fn round(&self) -> Result<Float, error::Error> {
+        FloatContext::with_default(|ctx| ctx.round(self))
+    }
+
+    /// Round the float to the nearest integral value, ties to even.
+    pub fn round_ties_even(&self) -> Result<Float, error::Error> {
+        FloatContext::with_default(|ctx| ctx.round_ties_even(self))
+    }
+
+    /// Get the IEEE remainder of the float divided by `rhs`, see [`FloatContext::remainder`].
+    ///
+    /// # Arguments
+    ///
+    /// * `rhs` - The divisor, must be of the same format.
+    pub fn remainder(&self, rhs: &Float) -> Result<Float, error::Error> {
+        FloatContext::with_default(|ctx| ctx.remainder(self, rhs))
+    }
+
+    /// Get the remainder of the float divided by `rhs`, like C `fmod`, see [`FloatContext::fmod`].
+    ///
+    /// # Arguments
+    ///
+    /// * `rhs` - The divisor, must be of the same format.
+    pub fn fmod(&self, rhs: &Float) -> Result<Float, error::Error> {
+        FloatContext::with_default(|ctx| ctx.fmod(self, rhs))
+    }
+
+    /// Get the smaller of the float and `rhs`, see [`FloatContext::minimum`].
+    ///
+    /// # Arguments
+    ///
+    /// * `rhs` - The float to compare with, must be of the same format.
+    pub fn minimum(&self, rhs: &Float) -> Result<Float, error::Error> {
+        FloatContext::with_default(|ctx| ctx.minimum(self, rhs))
+    }
+
+    /// Get the larger of the float and `rhs`, see [`FloatContext::maximum`].
+    ///
+    /// # Arguments
+    ///
+    /// * `rhs` - The float to compare with, must be of the same format.
+    pub fn maximum(&self, rhs: &Float) -> Result<Float, error::Error> {
+        FloatContext::with_default(|ctx| ctx.maximum(self, rhs))
+    }
+
+    /// Get the smaller of the float and `rhs` ignoring NaN, see [`FloatContext::minimum_number`].
+    ///
+    /// # Arguments
+    ///
+    /// * `rhs` - The float to compare with, must be of the same format.
+    pub fn minimum_number(&self, rhs: &Float) -> Result<Float, error::Error> {
+        FloatContext::with_default(|ctx| ctx.minimum_number(self, rhs))
+    }
+
+    /// Get the larger of the float and `rhs` ignoring NaN, see [`FloatContext::maximum_number`].
+    ///
+    /// # Arguments
+    ///
+    /// * `rhs` - The float to compare with, must be of the same format.
+    pub fn maximum_number(&self, rhs: &Float) -> Result<Float, error::Error> {
+        FloatContext::with_default(|ctx| ctx.maximum_number(self, rhs))
+    }
+
+    /// Get the canonical encoding of the float with the thread-local default context,
+    /// see [`FloatContext::canonicalize`].
+    pub fn canonicalize(&self) -> Result<Float, error::Error> {
+        FloatContext::with_default(|ctx| ctx.canonicalize(self))
+    }
+
+    /// Check if `rhs` is of the same format as the float.
+    fn check_format(&self, rhs: &Float) -> Result<(), error::Error> {
+        match self.format == rhs.format {
+            true => Ok(()),
+            false => Err(error::Error::MismatchedFormat),
+        }
+    }
+}
+
+impl FloatContext {
+    /// Add two floats of the same format.
+    /// The exact sum is rounded with the context.
+    ///
+    /// # Arguments
+    ///
+    /// * `lhs` - The first float.
+    /// * `rhs` - The second float, must be of the same format.
+    pub fn add(&mut self, lhs: &Float, rhs: &Float) -> Result<Float, error::Error> {
+        lhs.check_format(rhs)?;
+
+        let (x, y) = (self.decode(lhs), self.decode(rhs));
+        let sum = self.sum(x, y);
+        self.finish(&lhs.format, sum)
+    }
+
+    /// Subtract `rhs` from `lhs`, both of the same format.
+    /// The exact difference is rounded with the context.
+    ///
+    /// # Arguments
+    ///
+    /// * `lhs` - The float to subtract from.
+    /// * `rhs` - The float to subtract, must be of the same format.
+    pub fn sub(&mut self, lhs: &Float, rhs: &Float) -> Result<Float, error::Error> {
+        lhs.check_format(rhs)?;
+
+        let x = self.decode(lhs);
+        let y = match self.decode(rhs) {
+            Decoded::Finite { negative, value } => Decoded::Finite { negative: !negative, value },
+            Decoded::Infinity { negative } => Decoded::Infinity { negative: !negative },
+            nan => nan,
+        };
+
+        let sum = self.sum(x, y);
+        self.finish(&lhs.format, sum)
+    }
+
+    /// Multiply two floats of the same format.
+    /// The exact product is rounded with the context.
+    ///
+    /// # Arguments
+    ///
+    /// * `lhs` - The first float.
+    /// * `rhs` - The second float, must be of the same format.
+    pub fn mul(&mut self, lhs: &Float, rhs: &Float) -> Result<Float, error::Error> {
+        lhs.check_format(rhs)?;
+
+        let (x, y) = (self.decode(lhs), self.decode(rhs));
+        let product = self.product(x, y);
+        self.finish(&lhs.format, product)
+    }
+
+    /// Compute `x * a + b` of floats of the same format.
+    /// The exact result is rounded once with the context.
+    ///
+    /// # Arguments
+    ///
+    /// * `x` - The float to multiply.
+    /// * `a` - The float to multiply by, must be of the same format.
+    /// * `b` - The float to add to the product, must be of the same format.
+    pub fn mul_add(&mut self, x: &Float, a: &Float, b: &Float) -> Result<Float, error::Error> {
+        x.check_format(a)?;
+        x.check_format(b)?;
+
+        let (x_value, a_value, b_value) = (self.decode(x), self.decode(a), self.decode(b));
+        let product = self.product(x_value, a_value);
+        let sum = self.sum(product, b_value);
+        self.finish(&x.format, sum)
+    }
+
+    /// Divide `lhs` by `rhs`, both of the same format.
+    /// The exact quotient is rounded with the context.
+    ///
+    /// # Arguments
+    ///
+    /// * `lhs` - The dividend.
+    /// * `rhs` - The divisor, must be of the same format.
+    pub fn div(&mut self, lhs: &Float, rhs: &Float) -> Result<Float, error::Error> {
+        lhs.check_format(rhs)?;
+
+        let format = &lhs.format;
+        let zero = BigFraction::from(0u32);
+
+        let quot = match (self.decode(lhs), self.decode(rhs)) {
+            (lhs @ Decoded::NaN { .. }, rhs) | (lhs, rhs @ Decoded::NaN { .. }) => self.select_nan(lhs, rhs),
+            (Decoded::Infinity { .. }, Decoded::Infinity { .. }) => self.invalid(),
+            (Decoded::Finite { value: x, .. }, Decoded::Finite { value: y, .. }) if x == zero && y == zero => {
+                self.invalid()
+            },
+            (Decoded::Infinity { negative: a }, Decoded::Finite { negative: b, .. }) => {
+                Decoded::Infinity { negative: a != b }
+            },
+            (Decoded::Finite { negative: a, .. }, Decoded::Infinity { negative: b }) => {
+                Decoded::Finite { negative: a != b, value: zero }
+            },
+            (Decoded::Finite { negative: a, .. }, Decoded::Finite { negative: b, value: y }) if y == zero => {
+                self.flags.divide_by_zero = true;
+                return round::infinity_or_max(format, a != b, self).ok_or(error::Error::DivisionByZero);
+            },
+            (Decoded::Finite { negative: a, value: x }, Decoded::Finite { negative: b, value: y }) => {
+                Decoded::Finite { negative: a != b, value: x / y }
+            },
+        };
+
+        self.finish(format, quot)
+    }
+
+    /// Get the square root of the float.
+    /// The exact root is rounded with the context.
+    ///
+    /// The square root of a negative number is NaN, or an error if the format has no NaN.
+    pub fn sqrt(&mut self, float: &Float) -> Result<Float, error::Error> {
+        let format = &float.format;
+        let zero = BigFraction::from(0u32);
+
+        let root = match self.decode(float) {
+            Decoded::Finite { negative, value } if value == zero => Decoded::Finite { negative, value },
+            Decoded::Infinity { negative: true } | Decoded::Finite { negative: true, .. } => self.invalid(),
+            Decoded::Finite { value, .. } => {
+                Decoded::Finite { negative: false, value: sqrt(format, &value, self.rounding) }
+            },
+            other => other,
+        };
+
+        self.finish(format, root)
+    }
+
+    /// Get the reciprocal of the square root of the float.
+    /// The exact result is rounded with the context.
+    ///
+    /// The reciprocal square root of a negative number is NaN, or an error if the format has no NaN.
+    /// The reciprocal square root of a zero is the infinity of the same sign.
+    pub fn rsqrt(&mut self, float: &Float) -> Result<Float, error::Error> {
+        let format = &float.format;
+        let zero = BigFraction::from(0u32);
+
+        let root = match self.decode(float) {
+            Decoded::Finite { negative, value } if value == zero => {
+                self.flags.divide_by_zero = true;
+                return round::infinity_or_max(format, negative, self).ok_or(error::Error::DivisionByZero);
+            },
+            Decoded::Infinity { negative: true } | Decoded::Finite { negative: true, .. } => self.invalid(),
+            Decoded::Infinity { negative: false } => Decoded::Finite { negative: false, value: zero },
+            Decoded::Finite { value, .. } => {
+                Decoded::Finite { negative: false, value: sqrt(format, &value.recip(), self.rounding) }
+            },
+            nan => nan,
+        };
+
+        self.finish(format, root)
+    }
+
+    /// Round the float to an integral value toward negative infinity.
+    pub fn floor(&mut self, float: &Float) -> Result<Float, error::Error> {
+        self.integral(float, |negative, value| match negative {
+            true => value.ceil(),
+            false => value.floor(),
+        })
+    }
+
+    /// Round the float to an integral value toward positive infinity.
+    pub fn ceil(&mut self, float: &Float) -> Result<Float, error::Error> {
+        self.integral(float, |negative, value| match negative {
+            true => value.floor(),
+            false => value.ceil(),
+        })
+    }
+
+    /// Round the float to an integral value toward zero.
+    pub fn trunc(&mut self, float: &Float) -> Result<Float, error::Error> {
+        self.integral(float, |_, value| value.trunc())
+    }
+
+    /// Round the float to the nearest integral value, ties away from zero.
+    pub fn round(&mut self, float: &Float) -> Result<Float, error::Error> {
+        self.integral(float, |_, value| (value.clone() + BigFraction::new(1u32, 2u32)).floor())
+    }
+
+    /// Round the float to the nearest integral value, ties to even.
+    pub fn round_ties_even(&mut self, float: &Float) -> Result<Float, error::Error> {
+        self.integral(float, |_, value| round_ties_even(value))
+    }
+
+    /// Get the IEEE remainder of `lhs` divided by `rhs`, both of the same format.
+    /// The result is `lhs - n * rhs`, where `n` is the quotient rounded to the nearest integer, ties to even.
+    ///
+    /// # Arguments
+    ///
+    /// * `lhs` - The dividend.
+    /// * `rhs` - The divisor, must be of the same format.
+    pub fn remainder(&mut self, lhs: &Float, rhs: &Float) -> Result<Float, error::Error> {
+        lhs.check_format(rhs)?;
+        self.modulo(lhs, rhs, round_ties_even)
+    }
+
+    /// Get the remainder of `lhs` divided by `rhs`, both of the same format, like C `fmod`.
+    /// The result is `lhs - n * rhs`, where `n` is the quotient truncated toward zero.
+    ///
+    /// # Arguments
+    ///
+    /// * `lhs` - The dividend.
+    /// * `rhs` - The divisor, must be of the same format.
+    pub fn fmod(&mut self, lhs: &Float, rhs: &Float) -> Result<Float, error::Error> {
+        lhs.check_format(rhs)?;
+        self.modulo(lhs, rhs, |quot| quot.trunc())
+    }
+
+    /// Get the smaller of `lhs` and `rhs`, both of the same format, like IEEE 754-2019 `minimum`.
+    /// The result is NaN if either is NaN, and negative zero is smaller than positive zero.
+    ///
+    /// # Arguments
+    ///
+    /// * `lhs` - The first float.
+    /// * `rhs` - The second float, must be of the same format.
+    pub fn minimum(&mut self, lhs: &Float, rhs: &Float) -> Result<Float, error::Error> {
+        lhs.check_format(rhs)?;
+        self.min_max(lhs, rhs, std::cmp::Ordering::Less, false)
+    }
+
+    /// Get the larger of `lhs` and `rhs`, both of the same format, like IEEE 754-2019 `maximum`.
+    /// The result is NaN if either is NaN, and positive zero is larger than negative zero.
+    ///
+    /// # Arguments
+    ///
+    /// * `lhs` - The first float.
+    /// * `rhs` - The second float, must be of the same format.
+    pub fn maximum(&mut self, lhs: &Float, rhs: &Float) -> Result<Float, error::Error> {
+        lhs.check_format(rhs)?;
+        self.min_max(lhs, rhs, std::cmp::Ordering::Greater, false)
+    }
+
+    /// Get the smaller of `lhs` and `rhs`, both of the same format, like IEEE 754-2019 `minimumNumber`.
+    /// The result is NaN only if both are NaN, and negative zero is smaller than positive zero.
+    ///
+    /// # Arguments
+    ///
+    /// * `lhs` - The first float.
+    /// * `rhs` - The second float, must be of the same format.
+    pub fn minimum_number(&mut self, lhs: &Float, rhs: &Float) -> Result<Float, error::Error> {
+        lhs.check_format(rhs)?;
+        self.min_max(lhs, rhs, std::cmp::Ordering::Less, true)
+    }
+
+    /// Get the larger of `lhs` and `rhs`, both of the same format, like IEEE 754-2019 `maximumNumber`.
+    /// The result is NaN only if both are NaN, and positive zero is larger than negative zero.
+    ///
+    /// # Arguments
+    ///
+    /// * `lhs` - The first float.
+    /// * `rhs` - The second float, must be of the same format.
+    pub fn maximum_number(&mut self, lhs: &Float, rhs: &Float) -> Result<Float, error::Error> {
+        lhs.check_format(rhs)?;
+        self.min_max(lhs, rhs, std::cmp::Ordering::Greater, true)
+    }
+
+    /// Get the canonical encoding of the float.
+    /// Non-canonical finite values are rounded with the context, and a signaling NaN is quieted,
+    /// raising the invalid flag.
+    /// The sign and payload of NaN are kept if representable, otherwise it is the canonical quiet NaN.
+    pub fn canonicalize(&mut self, float: &Float) -> Result<Float, error::Error> {
+        match self.decode(float) {
+            Decoded::NaN { negative, payload: Some(payload), .. } => {
+                round::nan_with_payload(&float.format, negative, true, &payload)
+                    .or_else(|_| round::nan(&float.format).ok_or(error::Error::UnrepresentableNaN))
+            },
+            value => self.finish(&float.format, value),
+        }
+    }
+
+    /// Round the float to an integral value, keeping the sign.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - Map the sign and the magnitude to the integral magnitude.
+    fn integral(
+        &mut self,
+        float: &Float,
+        f: impl Fn(bool, &BigFraction) -> BigFraction,
+    ) -> Result<Float, error::Error> {
+        let value = match self.decode(float) {
+            Decoded::Finite { negative, value } => Decoded::Finite { negative, value: f(negative, &value) },
+            other => other,
+        };
+
+        self.finish(&float.format, value)
+    }
+
+    /// Select `lhs` or `rhs` by their order, a signaling NaN raises the invalid flag.
+    ///
+    /// # Arguments
+    ///
+    /// * `select` - The order of the selected float relative to the other.
+    /// * `number` - Whether a NaN is ignored if the other is not NaN.
+    fn min_max(
+        &mut self,
+        lhs: &Float,
+        rhs: &Float,
+        select: std::cmp::Ordering,
+        number: bool,
+    ) -> Result<Float, error::Error> {
+        let (x, y) = (self.decode(lhs), self.decode(rhs));
+
+        match (&x, &y) {
+            (Decoded::NaN { .. }, Decoded::NaN { .. }) => {},
+            (Decoded::NaN { .. }, _) if number => return Ok(self.operand(rhs)),
+            (_, Decoded::NaN { .. }) if number => return Ok(self.operand(lhs)),
+            _ => {},
+        }
+
+        // equal values are ordered by the sign bit, so negative zero is smaller
+        let order = match lhs.cmp_value(rhs) {
+            Some(std::cmp::Ordering::Equal) => rhs.is_sign_negative().cmp(&lhs.is_sign_negative()),
+            Some(order) => order,
+            None => {
+                let nan = self.select_nan(x, y);
+                return self.finish(&lhs.format, nan);
+            },
+        };
+
+        match order == select {
+            true => Ok(self.operand(lhs)),
+            false => Ok(self.operand(rhs)),
+        }
+    }
+
+    /// Get the operand selected as the result without rounding.
+    /// Subnormal values are zero of the same sign if denormals are zero in the context.
+    fn operand(&self, float: &Float) -> Float {
+        match self.denormals_are_zero && round::is_subnormal(float) {
+            true => round::encode(&float.format, float.is_sign_negative(), &BigUint::from(0u32)),
+            false => float.clone(),
+        }
+    }
+
+    /// Get the remainder of `lhs` divided by `rhs`, the sign of a zero result is the sign of `lhs`.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - Map the magnitude of the exact quotient to the magnitude of the integral quotient.
+    fn modulo(
+        &mut self,
+        lhs: &Float,
+        rhs: &Float,
+        f: impl Fn(&BigFraction) -> BigFraction,
+    ) -> Result<Float, error::Error> {
+        let zero = BigFraction::from(0u32);
+
+        let rem = match (self.decode(lhs), self.decode(rhs)) {
+            (lhs @ Decoded::NaN { .. }, rhs) | (lhs, rhs @ Decoded::NaN { .. }) => self.select_nan(lhs, rhs),
+            (Decoded::Infinity { .. }, _) => self.invalid(),
+            (_, Decoded::Finite { value, .. }) if value == zero => self.invalid(),
+            (lhs @ Decoded::Finite { .. }, Decoded::Infinity { .. }) => lhs,
+            (Decoded::Finite { negative: a, value: x }, Decoded::Finite { value: y, .. }) => {
+                // remainder of the magnitudes, which takes the sign of the float
+                let rem = x.clone() - f(&(x / y.clone())) * y;
+
+                let negative = match rem == zero {
+                    true => a,
+                    false => (rem < zero) != a,
+                };
+
+                Decoded::Finite { negative, value: rem.abs() }
+            },
+        };
+
+        self.finish(&lhs.format, rem)
+    }
+
+    /// Decode the float, raising the invalid flag for a signaling NaN.
+    /// Subnormal values are decoded as zero if denormals are zero in the context.
+    pub(crate) fn decode(&mut self, float: &Float) -> Decoded {
+        match float.decode() {
+            nan @ Decoded::NaN { quiet: false, .. } => {
+                self.flags.invalid = true;
+                nan
+            },
+            Decoded::Finite { negative, .. } if self.denormals_are_zero && round::is_subnormal(float) => {
+                Decoded::Finite { negative, value: BigFraction::from(0u32) }
+            },
+            other => other,
+        }
+    }
+
+    /// Raise the invalid flag and get the default NaN result.
+    fn invalid(&mut self) -> Decoded {
+        self.flags.invalid = true;
+        Decoded::NaN { negative: false, quiet: true, payload: None }
+    }
+
+    /// Select the NaN operand that propagates with the NaN profile of the context, one of them must be NaN.
+    fn select_nan(&self, lhs: Decoded, rhs: Decoded) -> Decoded {
+        match (self.nan.priority, &lhs, &rhs) {
+            (NanPriority::SignalingFirst, Decoded::NaN { quiet: true, .. }, Decoded::NaN { quiet: false, .. }) => rhs,
+            (_, Decoded::NaN { .. }, _) => lhs,
+            _ => rhs,
+        }
+    }
+
+    /// Get the NaN result in the format with the NaN profile of the context.
+    /// The propagated NaN is quieted, keeping the sign and payload if representable.
+    pub(crate) fn nan_result(
+        &self,
+        format: &Format,
+        negative: bool,
+        payload: Option<BigUint>,
+    ) -> Result<Float, error::Error> {
+        let propagated = match payload {
+            Some(payload) if self.nan.propagate => round::nan_with_payload(format, negative, true, &payload).ok(),
+            _ => None,
+        };
+
+        propagated
+            .or_else(|| round::default_nan(format, self.nan.default_nan))
+            .ok_or(error::Error::InvalidOperation)
+    }
+
+    /// Add the decoded values exactly.
+    /// The sum of infinities of different signs is NaN.
+    fn sum(&mut self, lhs: Decoded, rhs: Decoded) -> Decoded {
+        match (lhs, rhs) {
+            (lhs @ Decoded::NaN { .. }, rhs) | (lhs, rhs @ Decoded::NaN { .. }) => self.select_nan(lhs, rhs),
+            (Decoded::Infinity { negative: a }, Decoded::Infinity { negative: b }) if a != b => self.invalid(),
+            (inf @ Decoded::Infinity { .. }, _) | (_, inf @ Decoded::Infinity { .. }) => inf,
+            (Decoded::Finite { negative: a, value: x }, Decoded::Finite { negative: b, value: y }) => {
+                let sum = if a { -x } else { x } + if b { -y } else { y };
+
+                // exact zero sum is negative if both operands are negative,
+                // or if the operands have different signs and rounding toward negative
+                let negative = match sum == BigFraction::from(0u32) {
+                    true if a == b => a,
+                    true => self.rounding == RoundingMode::TowardNegative,
+                    false => sum < BigFraction::from(0u32),
+                };
+
+                Decoded::Finite { negative, value: sum.abs() }
+            },
+        }
+    }
+
+    /// Multiply the decoded values exactly.
+    /// The product of zero and infinity is NaN.
+    fn product(&mut self, lhs: Decoded, rhs: Decoded) -> Decoded {
+        let zero = BigFraction::from(0u32);
+
+        match (lhs, rhs) {
+            (lhs @ Decoded::NaN { .. }, rhs) | (lhs, rhs @ Decoded::NaN { .. }) => self.select_nan(lhs, rhs),
+            (Decoded::Infinity { .. }, Decoded::Finite { value, .. })
+            | (Decoded::Finite { value, .. }, Decoded::Infinity { .. }) if value == zero => self.invalid(),
+            (Decoded::Infinity { negative: a }, Decoded::Infinity { negative: b })
+            | (Decoded::Infinity { negative: a }, Decoded::Finite { negative: b, .. })
+            | (Decoded::Finite { negative: a, .. }, Decoded::Infinity { negative: b }) => {
+                Decoded::Infinity { negative: a != b }
+            },
+            (Decoded::Finite { negative: a, value: x }, Decoded::Finite { negative: b, value: y }) => {
+                Decoded::Finite { negative: a != b, value: x * y }
+            },
+        }
+    }
+
+    /// Round the decoded value into the format.
+    fn finish(&mut self, format: &Format, value: Decoded) -> Result<Float, error::Error> {
+        match value {
+            Decoded::Finite { negative, value } => round::round(format, negative, &value, self),
+            Decoded::Infinity { negative } => round::infinity_or_max(format, negative, self).ok_or(error::Error::OutOfRange),
+            Decoded::NaN { negative, payload, .. } => self.nan_result(format, negative, payload),
+        }
+    }
+}
+
+/// Get a value that rounds into the format the same as the square root of the positive `value`.
+///
+/// The root is truncated to at least 3 more bits than the mantissa, and half a unit is added if it is inexact,
+/// so the result is never on a rounding boundary unless the root is.
+/// For stochastic rounding the root is truncated to 128 more bits,
+/// so it rounds differently from the exact root only if the first 128 random bits match the discarded fraction.
+fn sqrt(format: &Format, value: &BigFraction, rounding: RoundingMode) -> BigFraction {
+    let extra = match rounding {
+        RoundingMode::Stochastic => 128,
+        _ => 3,
+    };
+    let shift = format.mant as i64 + extra - round::floor_log2(value).div_euclid(2);
+    let scaled = round::floor_scaled(value, 2 * shift);
+    let root = scaled.sqrt();
+
+    let exact = &root * &root == scaled
+        && BigFraction::new(scaled, BigUint::from(1u32)) == value.clone() * round::pow2(2 * shift);
+
+    BigFraction::new(root * 2u32 + !exact as u32, BigUint::from(1u32)) * round::pow2(-shift - 1)
+}
+
+/// Round the non-negative `value` to the nearest integer, ties to even.
+pub(crate) fn round_ties_even(value: &BigFraction) -> BigFraction {
+    let floor = value.floor();
+    let half = BigFraction::new(1u32, 2u32);
+
+    match (value.clone() - floor.clone()).partial_cmp(&half).unwrap() {
+        std::cmp::Ordering::Less => floor,
+        std::cmp::Ordering::Equal if floor.clone() % BigFraction::from(2u32) == BigFraction::from(0u32) => floor,
+        _ => floor + BigFraction::from(1u32),
+    }
+}
+
+impl std::ops::Add for Float {
+    type Output = Float;
+
+    /// Add two floats of the same format, see [`Float::add`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the formats differ or the sum cannot be represented in the format.
+    fn add(self, rhs: Float) -> Float {
+        Float::add(&self, &rhs).unwrap()
+    }
+}
+
+impl std::ops::Add for &Float {
+    type Output = Float;
+
+    /// Add two floats of the same format, see [`Float::add`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the formats differ or the sum cannot be represented in the format.
+    fn add(self, rhs: &Float) -> Float {
+        Float::add(self, rhs).unwrap()
+    }
+}
+
+impl std::ops::Sub for Float {
+    type Output = Float;
+
+    /// Subtract two floats of the same format, see [`Float::sub`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the formats differ or the difference cannot be represented in the format.
+    fn sub(self, rhs: Float) -> Float {
+        Float::sub(&self, &rhs).unwrap()
+    }
+}
+
+impl std::ops::Sub for &Float {
+    type Output = Float;
+
+    /// Subtract two floats of the same format, see [`Float::sub`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the formats differ or the difference cannot be represented in the format.
+    fn sub(self, rhs: &Float) -> Float {
+        Float::sub(self, rhs).unwrap()
+    }
+}
+
+impl std::ops::Mul for Float {
+    type Output = Float;
+
+    /// Multiply two floats of the same format, see [`Float::mul`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the formats differ or the product cannot be represented in the format.
+    fn mul(self, rhs: Float) -> Float {
+        Float::mul(&self, &rhs).unwrap()
+    }
+}
+
+impl std::ops::Mul for &Float {
+    type Output = Float;
+
+    /// Multiply two floats of the same format, see [`Float::mul`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the formats differ or the product cannot be represented in the format.
+    fn mul(self, rhs: &Float) -> Float {
+        Float::mul(self, rhs).unwrap()
+    }
+}
+
+impl std::ops::Div for Float {
+    type Output = Float;
+
+    /// Divide two floats of the same format, see [`Float::div`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the formats differ or the quotient cannot be represented in the format.
+    fn div(self, rhs: Float) -> Float {
+        Float::div(&self, &rhs).unwrap()
+    }
+}
+
+impl std::ops::Div for &Float {
+    type Output = Float;
+
+    /// Divide two floats of the same format, see [`Float::div`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the formats differ or the quotient cannot be represented in the format.
+    fn div(self, rhs: &Float) -> Float {
+        Float::div(self, rhs).unwrap()
+    }
+}
+
+impl std::ops::Neg for Float {
+    type Output = Float;
+
+    /// Flip the sign bit of the float, see [`Float::neg`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the format is unsigned.
+    fn neg(self) -> Float {
+        Float::neg(&self).unwrap()
+    }
+}
+
+impl std::ops::Neg for &Float {
+    type Output = Float;
+
+    /// Flip the sign bit of the float, see [`Float::neg`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the format is unsigned.
+    fn neg(self) -> Float {
+        Float::neg(self).unwrap()
+    }
+}
@@ -0,0 +1,50 @@
+/// Source of random bits for stochastic rounding.
+///
+/// Any type implementing [`Clone`] and this trait can be used in a [`FloatContext`](crate::FloatContext).
+pub trait RandomSource: RandomSourceClone {
+    /// Get the next 64 random bits.
+    fn next_u64(&mut self) -> u64;
+}
+
+/// Helper trait to clone a boxed [`RandomSource`], implemented for all cloneable random sources.
+pub trait RandomSourceClone {
+    /// Clone into a box.
+    fn clone_box(&self) -> Box<dyn RandomSource>;
+}
+
+impl<T: RandomSource + Clone + 'static> RandomSourceClone for T {
+    fn clone_box(&self) -> Box<dyn RandomSource> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn RandomSource> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
+
+/// The SplitMix64 pseudo random number generator, seeded with a 64 bit value.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct SplitMix64 {
+    /// The state of the generator.
+    pub state: u64,
+}
+
+impl SplitMix64 {
+    /// Create from the given seed.
+    pub fn new(seed: u64) -> SplitMix64 {
+        SplitMix64 { state: seed }
+    }
+}
+
+impl RandomSource for SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+}
@@ -0,0 +1,463 @@
+use crate::*;
+use fraction::{prelude::*, ToPrimitive};
+use std::cmp::Ordering;
+
+/// Rounding mode used when a value cannot be represented exactly in the format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum RoundingMode {
+    /// Round to the nearest value, ties to the value with an even last bit.
+    #[default]
+    NearestEven,
+
+    /// Round to the nearest value, ties away from zero.
+    NearestAway,
+
+    /// Round toward zero, i.e. truncate.
+    TowardZero,
+
+    /// Round toward positive infinity.
+    TowardPositive,
+
+    /// Round toward negative infinity.
+    TowardNegative,
+
+    /// Round to the neighbouring value with an odd last bit, unless the value is exact.
+    ToOdd,
+
+    /// Round up in magnitude with a probability equal to the fraction of the gap to the neighbouring values,
+    /// using the random source of the context.
+    Stochastic,
+}
+
+/// Decoded value of a float, with the magnitude of finite values kept exact.
+#[derive(Debug, Clone)]
+pub(crate) enum Decoded {
+    /// A finite value, including zero, with its sign and non-negative magnitude.
+    Finite { negative: bool, value: BigFraction },
+
+    /// An infinity with its sign.
+    Infinity { negative: bool },
+
+    /// Not a number with its sign bit, either quiet or signaling, with its payload.
+    /// The payload is none for the default NaN produced by invalid operations.
+    NaN { negative: bool, quiet: bool, payload: Option<BigUint> },
+}
+
+/// Get `2` to the power of `exp` as a fraction.
+pub(crate) fn pow2(exp: i64) -> BigFraction {
+    match exp < 0 {
+        true => BigFraction::new(BigUint::from(1u32), BigUint::from(1u32) << (-exp) as usize),
+        false => BigFraction::new(BigUint::from(1u32) << exp as usize, BigUint::from(1u32)),
+    }
+}
+
+/// Get the largest `e` such that `2^e <= value`, `value` must be positive.
+pub(crate) fn floor_log2(value: &BigFraction) -> i64 {
+    let numer = value.numer().unwrap();
+    let denom = value.denom().unwrap();
+
+    let exp = numer.bits() as i64 - denom.bits() as i64;
+    let below = match exp < 0 {
+        true => (numer << (-exp) as usize) < *denom,
+        false => *numer < (denom << exp as usize),
+    };
+
+    exp - below as i64
+}
+
+/// Get the floor of `value * 2^exp`, `value` must be non-negative.
+pub(crate) fn floor_scaled(value: &BigFraction, exp: i64) -> BigUint {
+    let numer = value.numer().unwrap();
+    let denom = value.denom().unwrap();
+
+    match exp < 0 {
+        true => numer / (denom << (-exp) as usize),
+        false => (numer << exp as usize) / denom,
+    }
+}
+
+/// Get the exact value of the grid index `k` of the format.
+///
+/// The grid index is the exponent and mantissa bits read as one unsigned integer,
+/// so the magnitudes of the format increase with it.
+pub(crate) fn grid_value(format: &Format, k: &BigUint) -> BigFraction {
+    if *k == BigUint::from(0u32) && format.specials.zero {
+        return BigFraction::from(0u32);
+    }
+
+    let exp = (k >> format.mant).to_i64().unwrap();
+    let mant = k - ((k >> format.mant) << format.mant);
+
+    // subnormals have the exponent of the smallest normal and no implicit leading 1
+    if exp == 0 && format.subnormal {
+        return BigFraction::new(mant, BigUint::from(1u32))
+            * pow2(1 - format.excess as i64 - format.mant as i64);
+    }
+
+    BigFraction::new((BigUint::from(1u32) << format.mant) + mant, BigUint::from(1u32))
+        * pow2(exp - format.excess as i64 - format.mant as i64)
+}
+
+/// Get the largest grid index whose value is not greater than `value`, `value` must be positive.
+fn grid_floor(format: &Format, value: &BigFraction) -> BigUint {
+    let exp = floor_log2(value);
+    let biased = exp + format.excess as i64;
+
+    if biased < 1 && format.subnormal {
+        return floor_scaled(value, format.excess as i64 - 1 + format.mant as i64);
+    }
+
+    if biased < 0 {
+        return BigUint::from(0u32);
+    }
+
+    (BigUint::from(biased as u64) << format.mant)
+        + floor_scaled(value, format.mant as i64 - exp)
+        - (BigUint::from(1u32) << format.mant)
+}
+
+/// Get the largest grid index of the format that encodes a finite value.
+pub(crate) fn grid_max(format: &Format) -> BigUint {
+    let all_ones = (BigUint::from(1u32) << (format.exp as usize + format.mant)) - 1u32;
+    let top_exp = (&all_ones >> format.mant) << format.mant;
+
+    match format.specials.nan {
+        _ if format.exp == 0 => all_ones,
+        NanEncoding::AllOnesExponent => top_exp - 1u32,
+        _ if format.specials.infinity => top_exp - 1u32,
+        NanEncoding::AllOnes => all_ones - 1u32,
+        NanEncoding::None | NanEncoding::NegativeZero => all_ones,
+    }
+}
+
+/// Get the grid index of the bit pattern of the float.
+pub(crate) fn grid_index(float: &Float) -> BigUint {
+    float.bits
+        .iter()
+        .skip(float.format.signed as usize)
+        .fold(BigUint::from(0u32), |acc, b| acc * 2u32 + if *b { 1u32 } else { 0u32 })
+}
+
+/// Create the float with the given sign and grid index.
+/// Zero is always positive if the format has no negative zero.
+pub(crate) fn encode(format: &Format, negative: bool, k: &BigUint) -> Float {
+    let width = format.exp as usize + format.mant;
+    let zero = format.specials.zero && *k == BigUint::from(0u32);
+    let negative = negative && (format.specials.negative_zero || !zero);
+    let sign = match format.signed {
+        true if negative => "1",
+        true => "0",
+        false => "",
+    };
+
+    let bits = BitPattern::from_bin_str(&format!("{}{:0width$b}", sign, k, width = width));
+    Float::from_bits(format.clone(), bits).unwrap()
+}
+
+/// Create the float from components, if the format decodes it as the expected value.
+fn special(format: &Format, negative: bool, exp: &str, mant: &str, expected: impl Fn(&Value) -> bool) -> Option<Float> {
+    let comps = Components::new_bin(
+        if format.signed { Some(negative) } else { None },
+        exp,
+        mant,
+    ).ok()?;
+
+    match expected(&format.decode(&comps)) {
+        true => Float::from_comps(format.clone(), comps).ok(),
+        false => None,
+    }
+}
+
+/// Get the infinity of the format with the given sign, if any.
+pub(crate) fn infinity(format: &Format, negative: bool) -> Option<Float> {
+    if !format.specials.infinity || (negative && !format.signed) {
+        return None;
+    }
+
+    special(
+        format,
+        negative,
+        &"1".repeat(format.exp as usize),
+        &"0".repeat(format.mant),
+        |value| *value == Value::Infinity { negative },
+    )
+}
+
+/// Get the payload of the canonical quiet NaN of the format.
+fn canonical_payload(format: &Format) -> BigUint {
+    match format.specials.quiet_bit {
+        QuietBit::Ieee2008 => BigUint::from(0u32),
+        QuietBit::Legacy => (BigUint::from(1u32) << format.mant.saturating_sub(1)) - 1u32,
+    }
+}
+
+/// Get the infinity of the format with the given sign,
+/// or the largest finite value if saturating with the context and the format has no infinity.
+pub(crate) fn infinity_or_max(format: &Format, negative: bool, ctx: &FloatContext) -> Option<Float> {
+    match ctx.overflow {
+        _ if negative && !format.signed => None,
+        OverflowPolicy::Saturate => {
+            infinity(format, negative).or_else(|| Some(encode(format, negative, &grid_max(format))))
+        },
+        OverflowPolicy::NaN => overflow_infinity(format, negative, ctx),
+        OverflowPolicy::Infinity | OverflowPolicy::Error => infinity(format, negative),
+    }
+}
+
+/// Get the infinity of the format with the given sign, or the default NaN of the context if the overflow policy is NaN.
+fn overflow_infinity(format: &Format, negative: bool, ctx: &FloatContext) -> Option<Float> {
+    match ctx.overflow {
+        OverflowPolicy::NaN => infinity(format, negative).or_else(|| default_nan(format, ctx.nan.default_nan)),
+        _ => infinity(format, negative),
+    }
+}
+
+/// Get the canonical quiet NaN of the format, if any.
+pub(crate) fn nan(format: &Format) -> Option<Float> {
+    nan_with_payload(format, false, true, &canonical_payload(format)).ok()
+}
+
+/// Get the default NaN of the format, if any.
+pub(crate) fn default_nan(format: &Format, default: DefaultNan) -> Option<Float> {
+    match default {
+        DefaultNan::Canonical => nan(format),
+        DefaultNan::NegativeCanonical => nan_with_payload(format, true, true, &canonical_payload(format)).ok(),
+        DefaultNan::AllOnes => {
+            let payload = (BigUint::from(1u32) << format.mant.saturating_sub(1)) - 1u32;
+            nan_with_payload(format, false, true, &payload).ok().or_else(|| nan(format))
+        },
+    }
+}
+
+/// Get the NaN of the format with the given sign bit, quiet bit and payload.
+/// The sign bit is ignored if the format has only one NaN.
+pub(crate) fn nan_with_payload(
+    format: &Format,
+    negative: bool,
+    quiet: bool,
+    payload: &BigUint,
+) -> Result<Float, error::Error> {
+    let zero = BigUint::from(0u32);
+
+    let (negative, exp, mant) = match format.specials.nan {
+        NanEncoding::AllOnesExponent if format.mant > 0 => {
+            if payload.bits() > format.mant - 1 {
+                return Err(error::Error::InsufficientMantissaBits);
+            }
+
+            let quiet_bit = quiet != (format.specials.quiet_bit == QuietBit::Legacy);
+            let payload = match format.mant {
+                1 => String::new(),
+                _ => format!("{:0width$b}", payload, width = format.mant - 1),
+            };
+
+            (negative, "1".repeat(format.exp as usize), (quiet_bit as u8).to_string() + &payload)
+        },
+        NanEncoding::AllOnes if quiet && *payload == zero => {
+            (negative, "1".repeat(format.exp as usize), "1".repeat(format.mant))
+        },
+        NanEncoding::NegativeZero if quiet && *payload == zero => {
+            (true, "0".repeat(format.exp as usize), "0".repeat(format.mant))
+        },
+        _ => return Err(error::Error::UnrepresentableNaN),
+    };
+
+    special(
+        format,
+        negative && format.signed,
+        &exp,
+        &mant,
+        |value| matches!(value, Value::NaN { quiet: q, payload: p } if *q == quiet && p == payload),
+    )
+    .ok_or(error::Error::UnrepresentableNaN)
+}
+
+/// Check if the float is subnormal, i.e. non-zero with an all zero exponent in a format with subnormals.
+pub(crate) fn is_subnormal(float: &Float) -> bool {
+    let k = grid_index(float);
+    float.format.subnormal && k != BigUint::from(0u32) && k >> float.format.mant == BigUint::from(0u32)
+}
+
+/// Check if rounding the value up from `lo_value` to `hi_value` with the rounding mode of the context.
+///
+/// # Arguments
+///
+/// * `odd` - Whether the last bit of `lo_value` is one.
+fn round_up(
+    ctx: &mut FloatContext,
+    negative: bool,
+    value: &BigFraction,
+    lo_value: &BigFraction,
+    hi_value: &BigFraction,
+    odd: bool,
+) -> bool {
+    match ctx.rounding {
+        RoundingMode::NearestEven | RoundingMode::NearestAway => {
+            match (value.clone() - lo_value.clone()).partial_cmp(&(hi_value.clone() - value.clone())).unwrap() {
+                Ordering::Less => false,
+                Ordering::Greater => true,
+                Ordering::Equal => ctx.rounding == RoundingMode::NearestAway || odd,
+            }
+        },
+        RoundingMode::TowardZero => false,
+        RoundingMode::TowardPositive => !negative,
+        RoundingMode::TowardNegative => negative,
+        RoundingMode::ToOdd => !odd,
+        RoundingMode::Stochastic => {
+            let mut frac = (value.clone() - lo_value.clone()) / (hi_value.clone() - lo_value.clone());
+
+            // compare with a uniform random number in [0, 1), 64 bits at a time
+            loop {
+                frac *= pow2(64);
+                let digits = frac.floor();
+                let random = BigFraction::from(ctx.random.next_u64());
+
+                match random.partial_cmp(&digits).unwrap() {
+                    Ordering::Less => break true,
+                    Ordering::Greater => break false,
+                    Ordering::Equal => frac -= digits,
+                }
+            }
+        },
+    }
+}
+
+/// Get the smallest positive normal magnitude of the format.
+pub(crate) fn min_normal(format: &Format) -> BigFraction {
+    pow2(format.subnormal as i64 - format.excess as i64)
+}
+
+/// Check if the non-zero value is tiny, with the tininess detection of the context.
+fn is_tiny(format: &Format, negative: bool, value: &BigFraction, ctx: &mut FloatContext) -> bool {
+    let min_normal = min_normal(format);
+
+    if *value >= min_normal {
+        return false;
+    }
+
+    // stochastic rounding is only detected before rounding, to not draw random bits twice
+    match ctx.tininess {
+        Tininess::BeforeRounding => true,
+        Tininess::AfterRounding if ctx.rounding == RoundingMode::Stochastic => true,
+        Tininess::AfterRounding => {
+            // round with the precision of the format, as if the exponent range were unbounded
+            let exp = floor_log2(value) - format.mant as i64;
+            let lo = floor_scaled(value, -exp);
+            let odd = &lo % 2u32 == BigUint::from(1u32);
+            let lo_value = BigFraction::new(lo, BigUint::from(1u32)) * pow2(exp);
+            let hi_value = lo_value.clone() + pow2(exp);
+
+            // not tiny only if rounded up to the smallest normal magnitude
+            *value == lo_value
+                || !round_up(ctx, negative, value, &lo_value, &hi_value, odd)
+                || hi_value < min_normal
+        },
+    }
+}
+
+/// Get zero with the given sign, or the smallest value by the underflow policy if the format has no zero.
+/// The smallest value is inexact, raising the underflow and inexact flags in the context.
+fn zero_or_min(format: &Format, negative: bool, ctx: &mut FloatContext) -> Result<Float, error::Error> {
+    match (format.specials.zero, ctx.underflow) {
+        (true, _) => Ok(encode(format, negative, &BigUint::from(0u32))),
+        (false, UnderflowPolicy::MinSubnormal) => {
+            ctx.flags.underflow = true;
+            ctx.flags.inexact = true;
+            Ok(encode(format, negative, &BigUint::from(0u32)))
+        },
+        (false, UnderflowPolicy::Zero | UnderflowPolicy::Error) => Err(error::Error::OutOfRange),
+    }
+}
+
+/// Round the exact value with the given sign and non-negative magnitude into the format.
+/// The value is rounded with the rounding mode of the context, raising the flags in the context.
+pub(crate) fn round(
+    format: &Format,
+    negative: bool,
+    value: &BigFraction,
+    ctx: &mut FloatContext,
+) -> Result<Float, error::Error> {
+    let zero = BigFraction::from(0u32);
+
+    if negative && !format.signed && *value != zero {
+        ctx.flags.invalid = true;
+        return Err(error::Error::NegativeSign);
+    }
+
+    if *value == zero {
+        return zero_or_min(format, negative, ctx);
+    }
+
+    let tiny = is_tiny(format, negative, value, ctx);
+
+    if tiny && ctx.flush_to_zero {
+        ctx.flags.underflow = true;
+        ctx.flags.inexact = true;
+        return zero_or_min(format, negative, ctx);
+    }
+
+    // below the smallest value of a format without zero, round between it and zero,
+    // zero would be at grid index -1 so a tie goes to the even smallest value
+    let min = grid_value(format, &BigUint::from(0u32));
+
+    if !format.specials.zero && *value < min {
+        ctx.flags.underflow = true;
+        ctx.flags.inexact = true;
+
+        return match round_up(ctx, negative, value, &zero, &min, true) {
+            true => Ok(encode(format, negative, &BigUint::from(0u32))),
+            false => zero_or_min(format, negative, ctx),
+        };
+    }
+
+    let lo = grid_floor(format, value);
+    let lo_value = grid_value(format, &lo);
+
+    let mut k = match lo_value == *value {
+        true => lo,
+        false => {
+            ctx.flags.inexact = true;
+            ctx.flags.underflow |= tiny;
+
+            let hi = &lo + 1u32;
+            let hi_value = grid_value(format, &hi);
+            let odd = &lo % 2u32 == BigUint::from(1u32);
+
+            match round_up(ctx, negative, value, &lo_value, &hi_value, odd) {
+                true => hi,
+                false => lo,
+            }
+        },
+    };
+
+    if k == BigUint::from(0u32) && format.specials.zero {
+        match ctx.underflow {
+            UnderflowPolicy::Zero => {},
+            UnderflowPolicy::MinSubnormal => k = BigUint::from(1u32),
+            UnderflowPolicy::Error => return Err(error::Error::OutOfRange),
+        }
+    }
+
+    let max = grid_max(format);
+
+    if k > max {
+        ctx.flags.overflow = true;
+        ctx.flags.inexact = true;
+
+        let to_infinity = match (ctx.overflow, ctx.rounding) {
+            (OverflowPolicy::Error, _) => return Err(error::Error::OutOfRange),
+            (OverflowPolicy::Saturate, _) => false,
+            (_, RoundingMode::NearestEven | RoundingMode::NearestAway | RoundingMode::Stochastic) => true,
+            (_, RoundingMode::TowardZero | RoundingMode::ToOdd) => false,
+            (_, RoundingMode::TowardPositive) => !negative,
+            (_, RoundingMode::TowardNegative) => negative,
+        };
+
+        return match to_infinity {
+            true => overflow_infinity(format, negative, ctx).ok_or(error::Error::OutOfRange),
+            false => Ok(encode(format, negative, &max)),
+        };
+    }
+
+    Ok(encode(format, negative, &k))
+}
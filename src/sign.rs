@@ -0,0 +1,69 @@
+use crate::*;
+use fraction::prelude::*;
+
+impl Float {
+    /// Flip the sign bit of the float, without rounding and keeping NaN as NaN.
+    /// The zero pattern is kept positive if the format has no negative zero.
+    ///
+    /// Returns an error if the format is unsigned.
+    pub fn neg(&self) -> Result<Float, error::Error> {
+        self.with_sign(!self.is_sign_negative())
+    }
+
+    /// Clear the sign bit of the float, without rounding and keeping NaN as NaN.
+    /// The float is unchanged if the format is unsigned.
+    pub fn abs(&self) -> Result<Float, error::Error> {
+        self.with_sign(false)
+    }
+
+    /// Set the sign bit of the float to the sign bit of `sign`, without rounding and keeping NaN as NaN.
+    ///
+    /// Returns an error if the format is unsigned and `sign` is negative.
+    ///
+    /// # Arguments
+    ///
+    /// * `sign` - The float to take the sign bit from, may be of a different format.
+    pub fn copysign(&self, sign: &Float) -> Result<Float, error::Error> {
+        self.with_sign(sign.is_sign_negative())
+    }
+
+    /// Get the sign of the float like [`f32::signum`], i.e. `1` if the sign bit is clear,
+    /// `-1` if the sign bit is set, and NaN unchanged.
+    ///
+    /// Returns an error if the format cannot represent one exactly.
+    pub fn signum(&self) -> Result<Float, error::Error> {
+        if self.is_nan() {
+            return Ok(self.clone());
+        }
+
+        let mut ctx = FloatContext::default();
+        let one = round::round(&self.format, self.is_sign_negative(), &BigFraction::from(1u32), &mut ctx)?;
+
+        match ctx.flags.inexact {
+            true => Err(error::Error::OutOfRange),
+            false => Ok(one),
+        }
+    }
+
+    /// Set the sign bit of the float, keeping the encoding valid for the format.
+    fn with_sign(&self, negative: bool) -> Result<Float, error::Error> {
+        if !self.format.signed {
+            return match negative {
+                true => Err(error::Error::NegativeSign),
+                false => Ok(self.clone()),
+            };
+        }
+
+        let zero = matches!(self.value(), Value::Zero { .. });
+        let negative = negative && (!zero || self.format.specials.negative_zero);
+
+        let mut float = self.clone();
+        float.bits.set(0, negative);
+
+        // the sign bit may be part of the NaN encoding
+        match float.is_nan() == self.is_nan() {
+            true => Ok(float),
+            false => Ok(self.clone()),
+        }
+    }
+}
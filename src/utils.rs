@@ -4,47 +4,11 @@ use fraction::{prelude::*, Num};
 
 /// Trait for structure that have an IEEE binary standard form.
 pub trait IeeeBinary {
+    fn ieee_binary16() -> Self;
     fn ieee_binary32() -> Self;
     fn ieee_binary64() -> Self;
-}
-
-/// Special interpretation of bit patterns for special values.
-/// This function is called during output.
-pub type Interpret = fn(&Components) -> Option<String>;
-
-/// Function to convert components to a string according to IEEE binary32 and binary64 format.
-fn ieee_interpret(comps: &Components) -> Option<String> {
-    match comps {
-        c if c.sign == Some(false) && c.exp.is_all_zero() && c.mant.is_all_zero() => {
-            Some("0".to_owned())
-        },
-        c if c.sign == Some(true) && c.exp.is_all_zero() && c.mant.is_all_zero() => {
-            Some("-0".to_owned())
-        },
-        c if c.sign == Some(false) && c.exp.is_all_one() && c.mant.is_all_zero() => {
-            Some("inf".to_owned())
-        },
-        c if c.sign == Some(true) && c.exp.is_all_one() && c.mant.is_all_zero() => {
-            Some("-inf".to_owned())
-        },
-        c if c.exp.is_all_one() && !c.mant.is_all_zero() && c.mant.iter().next().map(|b| *b) == Some(true) => {
-            Some("NaN".to_owned())
-        },
-        c if c.exp.is_all_one() && !c.mant.is_all_zero() && c.mant.iter().next().map(|b| *b) == Some(false) => {
-            Some("sNaN".to_owned())
-        },
-        _ => None,
-    }
-}
-
-impl IeeeBinary for Interpret {
-    fn ieee_binary32() -> Self {
-        ieee_interpret
-    }
-
-    fn ieee_binary64() -> Self {
-        ieee_interpret
-    }
+    fn ieee_binary128() -> Self;
+    fn ieee_binary256() -> Self;
 }
 
 pub type BitPattern = BitVec<usize, Msb0>;
@@ -125,7 +89,7 @@ impl BitPatternExt for BitPattern {
     }
 
     fn from_dec_str(s: &str) -> Self {
-        let s = s.chars().filter(|c| c.is_digit(10)).collect::<String>();
+        let s = s.chars().filter(|c| c.is_ascii_digit()).collect::<String>();
 
         let mut int = BigUint::from_str_radix(&s, 10).unwrap();
         let mut bits = String::new();
@@ -158,7 +122,7 @@ impl BitPatternExt for BitPattern {
     fn from_hex_str(s: &str) -> Self {
         s
             .chars()
-            .filter(|c| c.is_digit(16))
+            .filter(|c| c.is_ascii_hexdigit())
             .flat_map(|c| format!("{:4b}", c.to_digit(16).unwrap())
                 .chars()
                 .collect::<Vec<_>>()
@@ -207,7 +171,7 @@ impl BitPatternExt for BitPattern {
             .chunks(4)
             .map(|c| format!("{:x}", c)
                 .chars()
-                .filter(|c| c.is_digit(16))
+                .filter(|c| c.is_ascii_hexdigit())
                 .collect::<String>()
             )
             .collect()
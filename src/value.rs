@@ -0,0 +1,24 @@
+use crate::*;
+use fraction::prelude::*;
+
+/// Value of a float decoded with the semantics of its format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    /// A finite non-zero value, kept exact.
+    Finite(BigFraction),
+
+    /// A zero with its sign.
+    Zero { negative: bool },
+
+    /// An infinity with its sign.
+    Infinity { negative: bool },
+
+    /// Not a number, either quiet or signaling, with the payload in the remaining mantissa bits.
+    NaN { quiet: bool, payload: BigUint },
+}
+
+/// Trait for structure that decode the components of a float into its value.
+pub trait Decode {
+    /// Decode the components into the value.
+    fn decode(&self, comps: &Components) -> Value;
+}
@@ -0,0 +1,71 @@
+use float_format::*;
+use std::num::FpCategory;
+
+mod common;
+use common::bits;
+
+#[test]
+fn classify_like_f32() {
+    let values = [
+        0f32, -0f32, 1f32, -2.5f32, f32::MAX, f32::MIN_POSITIVE, f32::from_bits(1), -f32::MIN_POSITIVE / 2f32,
+        f32::INFINITY, f32::NEG_INFINITY, f32::NAN, -f32::NAN, f32::from_bits(0x7f800001),
+    ];
+
+    for x in values {
+        let float = Float::from(x);
+
+        assert_eq!(float.classify(), x.classify(), "{}", x);
+        assert_eq!(float.is_nan(), x.is_nan(), "{}", x);
+        assert_eq!(float.is_infinite(), x.is_infinite(), "{}", x);
+        assert_eq!(float.is_finite(), x.is_finite(), "{}", x);
+        assert_eq!(float.is_normal(), x.is_normal(), "{}", x);
+        assert_eq!(float.is_subnormal(), x.is_subnormal(), "{}", x);
+        assert_eq!(float.is_sign_negative(), x.is_sign_negative(), "{}", x);
+        assert_eq!(float.is_sign_positive(), x.is_sign_positive(), "{}", x);
+        assert!(float.is_canonical());
+    }
+}
+
+#[test]
+fn classify_custom_formats() {
+    // every bit pattern is finite
+    let format = Format::new(4, 3, 7);
+    assert_eq!(bits(&format, "7f").classify(), FpCategory::Normal);
+    assert_eq!(bits(&format, "87").category(), Category::Subnormal);
+    assert_eq!(bits(&format, "80").category(), Category::Zero);
+
+    // without subnormals, the all zero exponent is normal
+    let format = Format { subnormal: false, ..Format::new(4, 3, 7) };
+    assert_eq!(bits(&format, "01").category(), Category::Normal);
+    assert_eq!(bits(&format, "00").category(), Category::Zero);
+
+    // the negative zero pattern is either NaN or a non-canonical zero
+    let format = Format {
+        specials: Specials { infinity: false, nan: NanEncoding::NegativeZero, negative_zero: false, ..Specials::finite() },
+        ..Format::new(4, 3, 8)
+    };
+    assert_eq!(bits(&format, "80").category(), Category::Nan);
+    assert!(bits(&format, "80").is_sign_negative());
+
+    let format = Format {
+        specials: Specials { negative_zero: false, ..Specials::finite() },
+        ..Format::new(4, 3, 7)
+    };
+    assert_eq!(bits(&format, "80").category(), Category::NonCanonicalZero);
+    assert_eq!(bits(&format, "80").classify(), FpCategory::Zero);
+    assert!(!bits(&format, "80").is_canonical());
+
+    // finite values beside the infinities are never the result of rounding
+    let format = Format {
+        specials: Specials { infinity: true, nan: NanEncoding::AllOnes, ..Specials::finite() },
+        ..Format::new(4, 3, 7)
+    };
+    assert_eq!(bits(&format, "78").category(), Category::Infinite);
+    assert_eq!(bits(&format, "7a").category(), Category::NonCanonicalFinite);
+    assert_eq!(bits(&format, "7a").classify(), FpCategory::Normal);
+    assert_eq!(bits(&format, "ff").category(), Category::Nan);
+
+    // unsigned formats are never negative
+    let format = Format::new_unsigned(4, 3, 7);
+    assert!(bits(&format, "7f").is_sign_positive());
+}
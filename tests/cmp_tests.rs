@@ -0,0 +1,124 @@
+use float_format::*;
+use std::cmp::Ordering;
+
+mod common;
+use common::{bits, hex};
+
+#[test]
+fn value_ordering() {
+    let values = [-f32::INFINITY, -2.5f32, -f32::from_bits(1), -0f32, 0f32, f32::from_bits(1), 1f32, f32::MAX, f32::INFINITY, f32::NAN];
+
+    for a in values {
+        for b in values {
+            let (x, y) = (Numeric(Float::from(a)), Numeric(Float::from(b)));
+            assert_eq!(x.partial_cmp(&y), a.partial_cmp(&b), "{} cmp {}", a, b);
+            assert_eq!(x == y, a == b, "{} == {}", a, b);
+        }
+    }
+
+    // values of different formats are compared exactly
+    let fp8 = Format::new(4, 3, 7);
+    let x = Numeric(Float::from_str(fp8.clone(), "1.125").unwrap());
+    assert_eq!(x, Numeric(Float::from(1.125f32)));
+    assert!(x < Numeric(Float::from(1.1250001f32)));
+    assert!(Numeric(Float::from_str(fp8.clone(), "-0").unwrap()) == Numeric(Float::from(0f32)));
+}
+
+#[test]
+fn total_order() {
+    let values = [
+        f32::from_bits(0xffc00001), f32::from_bits(0xffc00000), f32::from_bits(0xff800001), -f32::INFINITY,
+        -f32::MAX, -1f32, -f32::from_bits(1), -0f32, 0f32, f32::from_bits(1), 1f32, f32::MAX, f32::INFINITY,
+        f32::from_bits(0x7f800001), f32::from_bits(0x7fc00000), f32::from_bits(0x7fc00001),
+    ];
+
+    for a in values {
+        for b in values {
+            assert_eq!(Float::from(a).total_cmp(&Float::from(b)), a.total_cmp(&b), "{:x} cmp {:x}", a.to_bits(), b.to_bits());
+        }
+    }
+
+    let mut floats: Vec<_> = values.iter().rev().map(|x| Float::from(*x)).collect();
+    floats.sort_by(Float::total_cmp);
+    assert_eq!(floats, values.map(Float::from));
+
+    // non-canonical zero is ordered by the sign bit
+    let format = Format { specials: Specials { negative_zero: false, ..Specials::finite() }, ..Format::new(4, 3, 7) };
+    assert_eq!(bits(&format, "80").total_cmp(&bits(&format, "00")), Ordering::Less);
+    assert_eq!(bits(&format, "81").total_cmp(&bits(&format, "80")), Ordering::Less);
+}
+
+#[test]
+fn minimum_and_maximum() {
+    let (one, two, zero) = (Float::from(1f32), Float::from(2f32), Float::from(0f32));
+    let (qnan, snan) = (Float::from(f32::from_bits(0x7fc00001)), Float::from(f32::from_bits(0x7f800002)));
+
+    assert_eq!(one.minimum(&two).unwrap(), one);
+    assert_eq!(two.maximum(&one).unwrap(), two);
+    assert_eq!((-&one).maximum(&-&two).unwrap(), -&one);
+    assert_eq!(zero.minimum(&-&zero).unwrap(), -&zero);
+    assert_eq!((-&zero).maximum(&zero).unwrap(), zero);
+    assert_eq!(zero.minimum_number(&-&zero).unwrap(), -&zero);
+    assert_eq!(Float::from(f32::NEG_INFINITY).minimum(&one).unwrap(), Float::from(f32::NEG_INFINITY));
+
+    assert_eq!(hex(one.minimum(&qnan)), Ok("7fc00001".to_owned()));
+    assert_eq!(hex(qnan.maximum(&one)), Ok("7fc00001".to_owned()));
+    assert_eq!(one.minimum_number(&qnan).unwrap(), one);
+    assert_eq!(qnan.maximum_number(&two).unwrap(), two);
+    assert_eq!(hex(qnan.minimum_number(&Float::from(f32::NAN))), Ok("7fc00001".to_owned()));
+
+    let mut ctx = FloatContext::default();
+    assert_eq!(hex(ctx.maximum(&one, &snan)), Ok("7fc00002".to_owned()));
+    assert!(ctx.flags.invalid);
+
+    let mut ctx = FloatContext::default();
+    assert_eq!(ctx.minimum_number(&snan, &two).unwrap(), two);
+    assert!(ctx.flags.invalid);
+
+    // the selected operand is not rounded with the context
+    let mut ctx = FloatContext { overflow: OverflowPolicy::Saturate, ..Default::default() };
+    let inf = Float::from(f32::INFINITY);
+    assert_eq!(ctx.maximum(&inf, &one).unwrap(), inf);
+    assert_eq!(ctx.minimum_number(&-&inf, &qnan).unwrap(), -&inf);
+    assert!(ctx.flags.is_empty());
+
+    assert_eq!(one.minimum(&Float::from_str(Format::new(4, 3, 7), "1").unwrap()), Err(error::Error::MismatchedFormat));
+
+    // formats without NaN
+    let fp8 = Format::new(4, 3, 7);
+    let (a, b) = (Float::from_str(fp8.clone(), "-3").unwrap(), Float::from_str(fp8.clone(), "0.5").unwrap());
+    assert_eq!(a.minimum(&b).unwrap(), a);
+    assert_eq!(a.maximum_number(&b).unwrap(), b);
+}
+
+#[test]
+fn cross_format_values() {
+    let bfloat16 = Format { specials: Specials::ieee(), ..Format::new(8, 7, 127) };
+    let wide = Format::new(16, 64, 16383);
+    let fp8 = Format::new(4, 3, 7);
+
+    let third = Float::from_str(wide.clone(), &format!("{:.40}", 1f64 / 3f64)).unwrap();
+    let third16 = third.convert_to(bfloat16.clone(), RoundingMode::NearestEven).unwrap();
+
+    // a rounding conversion changes the value, converting back exactly does not
+    assert!(!third16.equals_value(&third));
+    assert_eq!(third16.cmp_value(&third), Some(Ordering::Greater));
+    assert!(third16.convert_to(wide.clone(), RoundingMode::NearestEven).unwrap().equals_value(&third16));
+    assert!(third16.convert_to(Format::ieee_binary32(), RoundingMode::NearestEven).unwrap().equals_value(&third16));
+
+    // differences beyond the precision of f64
+    let one = Float::from_str(wide.clone(), "1").unwrap();
+    let above = Float::from_str(wide.clone(), &format!("1.{}1", "0".repeat(18))).unwrap();
+    assert_eq!(one.to_f64(), above.to_f64());
+    assert_eq!(Float::from(1f32).cmp_value(&above), Some(Ordering::Less));
+    assert!(Float::from(1f32).equals_value(&one));
+
+    // zeros, infinities and NaN
+    assert!(Float::from(-0f32).equals_value(&Float::from_str(fp8.clone(), "0").unwrap()));
+    assert!(Float::from(f32::NAN).equals_value(&Float::from(f32::from_bits(0xff800001))));
+    assert!(!Float::from(f32::NAN).equals_value(&Float::from(1f32)));
+    assert_eq!(Float::from(f32::NAN).cmp_value(&Float::from(f32::NAN)), None);
+    assert_eq!(Float::from(f32::INFINITY).cmp_value(&Float::from_str(wide.clone(), &"9".repeat(1000)).unwrap()), Some(Ordering::Greater));
+    assert!(!Float::from(f32::INFINITY).equals_value(&Float::from(f32::NEG_INFINITY)));
+    assert_eq!(Float::from_str(fp8.clone(), "-480").unwrap().cmp_value(&Float::from(f32::NEG_INFINITY)), Some(Ordering::Greater));
+}
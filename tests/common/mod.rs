@@ -0,0 +1,13 @@
+#![allow(dead_code)]
+
+use float_format::*;
+
+/// Create a float of the format from the given hexadecimal bit pattern.
+pub fn bits(format: &Format, s: &str) -> Float {
+    Float::from_bits(format.clone(), BitPattern::from_hex_str(s)).unwrap()
+}
+
+/// Get the hexadecimal bit pattern of the resulting float.
+pub fn hex(result: Result<Float, error::Error>) -> Result<String, error::Error> {
+    result.map(|float| float.bits.to_hex_string())
+}
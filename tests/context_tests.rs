@@ -0,0 +1,283 @@
+use float_format::*;
+
+mod common;
+use common::hex;
+
+#[test]
+fn exception_flags() {
+    let mut ctx = FloatContext::default();
+    let (one, three) = (Float::from(1f32), Float::from(3f32));
+
+    ctx.add(&one, &three).unwrap();
+    assert!(ctx.flags.is_empty());
+
+    ctx.div(&one, &three).unwrap();
+    assert_eq!(ctx.flags, Flags { inexact: true, ..Default::default() });
+
+    ctx.clear_flags();
+    ctx.div(&one, &Float::from(0f32)).unwrap();
+    assert_eq!(ctx.flags, Flags { divide_by_zero: true, ..Default::default() });
+
+    ctx.clear_flags();
+    ctx.sqrt(&Float::from(-1f32)).unwrap();
+    assert_eq!(ctx.flags, Flags { invalid: true, ..Default::default() });
+
+    ctx.clear_flags();
+    ctx.mul(&Float::from(f32::MAX), &three).unwrap();
+    assert_eq!(ctx.flags, Flags { overflow: true, inexact: true, ..Default::default() });
+
+    ctx.clear_flags();
+    ctx.mul(&Float::from(1e-30f32), &Float::from(1e-30f32)).unwrap();
+    assert_eq!(ctx.flags, Flags { underflow: true, inexact: true, ..Default::default() });
+
+    ctx.clear_flags();
+    let snan = Float::from_bits(Format::ieee_binary32(), BitPattern::from_str("0x7fa00000").unwrap()).unwrap();
+    ctx.add(&snan, &one).unwrap();
+    assert_eq!(ctx.flags, Flags { invalid: true, ..Default::default() });
+
+    ctx.clear_flags();
+    ctx.add(&Float::from(f32::NAN), &one).unwrap();
+    assert!(ctx.flags.is_empty());
+
+    ctx.clear_flags();
+    assert_eq!(ctx.from_str(Format::ieee_binary32(), "0.5").unwrap(), Float::from(0.5f32));
+    assert!(ctx.flags.is_empty());
+    ctx.from_str(Format::ieee_binary32(), "0.1").unwrap();
+    assert_eq!(ctx.flags, Flags { inexact: true, ..Default::default() });
+
+    ctx.clear_flags();
+    let format = Format::new(4, 3, 7);
+    assert_eq!(ctx.convert(&Float::from(f32::MAX), format.clone()), Err(error::Error::OutOfRange));
+    assert_eq!(ctx.flags, Flags { overflow: true, inexact: true, ..Default::default() });
+}
+
+#[test]
+fn rounding_mode() {
+    let mut ctx = FloatContext::new(RoundingMode::TowardNegative);
+    let (one, three) = (Float::from(1f32), Float::from(3f32));
+
+    // 1/3 is rounded up when rounding to nearest
+    assert_eq!(ctx.div(&one, &three).unwrap(), Float::from(f32::from_bits((1f32 / 3f32).to_bits() - 1)));
+    ctx.rounding = RoundingMode::TowardPositive;
+    assert_eq!(ctx.div(&one, &three).unwrap(), Float::from(1f32 / 3f32));
+
+    // exact zero sums are negative when rounding toward negative infinity
+    ctx.rounding = RoundingMode::TowardNegative;
+    assert_eq!(ctx.sub(&one, &one).unwrap(), Float::from(-0f32));
+    ctx.rounding = RoundingMode::NearestEven;
+    assert_eq!(ctx.sub(&one, &one).unwrap(), Float::from(0f32));
+}
+
+#[test]
+fn default_context() {
+    std::thread::spawn(|| {
+        let (one, three) = (Float::from(1f32), Float::from(3f32));
+
+        FloatContext::set_default(FloatContext::new(RoundingMode::TowardPositive));
+        assert_eq!(&one / &three, Float::from(1f32 / 3f32));
+        assert_eq!(&-&one / &three, Float::from(f32::from_bits((-1f32 / 3f32).to_bits() - 1)));
+        assert_eq!(FloatContext::get_default().flags, Flags { inexact: true, ..Default::default() });
+
+        // explicit rounding modes do not change the default rounding mode
+        Float::from_str_with_rounding(Format::ieee_binary32(), "0.1", RoundingMode::TowardZero).unwrap();
+        assert_eq!(FloatContext::get_default().rounding, RoundingMode::TowardPositive);
+
+        let prev = FloatContext::set_default(FloatContext::default());
+        assert_eq!(prev.rounding, RoundingMode::TowardPositive);
+        assert!(FloatContext::with_default(|ctx| ctx.flags.is_empty()));
+    }).join().unwrap();
+}
+
+#[test]
+fn subnormal_handling() {
+    let format = Format::new(4, 3, 7);
+
+    // rounds up to the smallest normal magnitude 2^-6
+    let parse = |ctx: &mut FloatContext| ctx.from_str(format.clone(), "0.015380859375").unwrap().bits.to_bin_string();
+
+    let mut ctx = FloatContext::default();
+    assert_eq!(parse(&mut ctx), "00001000");
+    assert_eq!(ctx.flags, Flags { inexact: true, ..Default::default() });
+
+    let mut ctx = FloatContext { tininess: Tininess::BeforeRounding, ..Default::default() };
+    assert_eq!(parse(&mut ctx), "00001000");
+    assert_eq!(ctx.flags, Flags { inexact: true, underflow: true, ..Default::default() });
+
+    let mut ctx = FloatContext { flush_to_zero: true, ..Default::default() };
+    assert_eq!(parse(&mut ctx), "00001000");
+
+    let mut ctx = FloatContext { flush_to_zero: true, tininess: Tininess::BeforeRounding, ..Default::default() };
+    assert_eq!(parse(&mut ctx), "00000000");
+    assert_eq!(ctx.flags, Flags { inexact: true, underflow: true, ..Default::default() });
+
+    let mut ctx = FloatContext { flush_to_zero: true, ..Default::default() };
+    assert_eq!(ctx.from_str(format.clone(), "-0.01").unwrap().bits.to_bin_string(), "10000000");
+
+    let tiny = Float::from_bits(Format::ieee_binary32(), BitPattern::from_str("0x00000001").unwrap()).unwrap();
+    let big = Float::from(2f32.powi(100));
+
+    let mut ctx = FloatContext::default();
+    assert_ne!(ctx.mul(&tiny, &big).unwrap(), Float::from(0f32));
+
+    let mut ctx = FloatContext { denormals_are_zero: true, ..Default::default() };
+    assert_eq!(ctx.mul(&tiny, &big).unwrap(), Float::from(0f32));
+    assert_eq!(ctx.mul(&-&tiny, &big).unwrap(), Float::from(-0f32));
+    assert_eq!(ctx.convert(&tiny, Format::ieee_binary64()).unwrap(), Float::from_str(Format::ieee_binary64(), "0").unwrap());
+    assert_eq!(ctx.sqrt(&Float::from(f32::from_bits(5))).unwrap(), Float::from(0f32));
+    assert_eq!(ctx.sqrt(&-&tiny).unwrap(), Float::from(-0f32));
+    assert_eq!(ctx.fmod(&tiny, &Float::from(f32::INFINITY)).unwrap(), Float::from(0f32));
+    assert_eq!(ctx.minimum(&tiny, &Float::from(1f32)).unwrap(), Float::from(0f32));
+    assert_eq!(ctx.minimum(&-&tiny, &Float::from(0f32)).unwrap(), Float::from(-0f32));
+    assert_eq!(ctx.maximum_number(&Float::from(f32::NAN), &tiny).unwrap(), Float::from(0f32));
+    assert!(ctx.flags.is_empty());
+
+    // the selected operand is not a result to flush
+    let mut ctx = FloatContext { flush_to_zero: true, ..Default::default() };
+    let five = Float::from(f32::from_bits(5));
+    assert_eq!(ctx.maximum(&five, &Float::from(-1f32)).unwrap(), five);
+    assert_eq!(ctx.minimum_number(&tiny, &Float::from(f32::NAN)).unwrap(), tiny);
+    assert!(ctx.flags.is_empty());
+}
+
+#[test]
+fn stochastic_rounding() {
+    assert_eq!(SplitMix64::new(0).next_u64(), 0xe220a8397b1dcdaf);
+
+    let format = Format::new(4, 3, 7);
+    let mut ctx = FloatContext {
+        rounding: RoundingMode::Stochastic,
+        random: Box::new(SplitMix64::new(42)),
+        ..Default::default()
+    };
+
+    // 1.0625 and 1.03125 are a half and a quarter of the way from 1 to 1.125
+    let count_up = |ctx: &mut FloatContext, s: &str| {
+        (0..4000)
+            .filter(|_| ctx.from_str(format.clone(), s).unwrap().bits.to_bin_string() == "00111001")
+            .count()
+    };
+
+    let up = count_up(&mut ctx, "1.0625");
+    assert!((1800..2200).contains(&up), "{}", up);
+
+    let up = count_up(&mut ctx, "1.03125");
+    assert!((800..1200).contains(&up), "{}", up);
+
+    assert_eq!(count_up(&mut ctx, "1.125"), 4000);
+    assert_eq!(count_up(&mut ctx, "1"), 0);
+
+    // the same seed gives the same results
+    let mut other = ctx.clone();
+    let x = Float::from(1f32 / 3f32);
+    for _ in 0..100 {
+        assert_eq!(ctx.convert(&x, format.clone()), other.convert(&x, format.clone()));
+    }
+
+    let mut ctx = FloatContext { rounding: RoundingMode::Stochastic, ..Default::default() };
+    let (one, three) = (Float::from(1f32), Float::from(3f32));
+    let results = (0..100).map(|_| ctx.div(&one, &three).unwrap()).collect::<Vec<_>>();
+    let down = Float::from(f32::from_bits((1f32 / 3f32).to_bits() - 1));
+    assert!(results.iter().all(|r| *r == down || *r == Float::from(1f32 / 3f32)));
+    assert!(results.contains(&down));
+
+    // the square root of 2 is about 0.1547 of the way from 1.4140625 to 1.4150390625
+    let format = Format::new(5, 10, 15);
+    let two = Float::from_str(format.clone(), "2").unwrap();
+    let sqrt_up = Float::from_str(format.clone(), "1.4150390625").unwrap();
+    let rsqrt_up = Float::from_str(format.clone(), "0.70751953125").unwrap();
+
+    let up = (0..3000).filter(|_| ctx.sqrt(&two).unwrap() == sqrt_up).count();
+    assert!((400..530).contains(&up), "{}", up);
+
+    let up = (0..3000).filter(|_| ctx.rsqrt(&two).unwrap() == rsqrt_up).count();
+    assert!((400..530).contains(&up), "{}", up);
+}
+
+#[test]
+fn nan_profiles() {
+    let nan = |bits: u32| Float::from(f32::from_bits(bits));
+    let (a, b, s, one, zero) = (nan(0x7fc00001), nan(0x7fc00002), nan(0x7f800003), Float::from(1f32), Float::from(0f32));
+    let ctx = |nan: NanProfile| FloatContext { nan, ..Default::default() };
+
+    let mut x86 = ctx(NanProfile::x86_sse());
+    assert_eq!(hex(x86.add(&a, &b)), Ok("7fc00001".to_owned()));
+    assert_eq!(hex(x86.mul(&b, &a)), Ok("7fc00002".to_owned()));
+    assert_eq!(hex(x86.sub(&a, &s)), Ok("7fc00001".to_owned()));
+    assert_eq!(hex(x86.div(&one, &s)), Ok("7fc00003".to_owned()));
+    assert_eq!(hex(x86.add(&-&a, &one)), Ok("ffc00001".to_owned()));
+    assert_eq!(hex(x86.div(&zero, &zero)), Ok("ffc00000".to_owned()));
+    assert_eq!(hex(x86.sqrt(&Float::from(-1f32))), Ok("ffc00000".to_owned()));
+    assert_eq!(hex(x86.mul_add(&one, &b, &a)), Ok("7fc00002".to_owned()));
+    assert_eq!(hex(x86.convert(&a, Format::ieee_binary64())), Ok("7ff8000020000000".to_owned()));
+
+    let mut arm = ctx(NanProfile::arm());
+    assert_eq!(hex(arm.add(&a, &b)), Ok("7fc00001".to_owned()));
+    assert_eq!(hex(arm.add(&a, &s)), Ok("7fc00003".to_owned()));
+    assert_eq!(hex(arm.div(&zero, &zero)), Ok("7fc00000".to_owned()));
+
+    for profile in [NanProfile::arm_default_nan(), NanProfile::riscv()] {
+        let mut ctx = ctx(profile);
+        assert_eq!(hex(ctx.add(&a, &b)), Ok("7fc00000".to_owned()));
+        assert_eq!(hex(ctx.add(&-&s, &one)), Ok("7fc00000".to_owned()));
+        assert_eq!(hex(ctx.convert(&a, Format::ieee_binary64())), Ok("7ff8000000000000".to_owned()));
+        assert!(ctx.flags.invalid);
+    }
+
+    let mut ptx = ctx(NanProfile::nvidia_ptx());
+    assert_eq!(hex(ptx.add(&a, &b)), Ok("7fffffff".to_owned()));
+    assert_eq!(hex(ptx.div(&zero, &zero)), Ok("7fffffff".to_owned()));
+    assert_eq!(hex(ptx.convert(&a, Format::ieee_binary64())), Ok("7fffffffffffffff".to_owned()));
+
+    // the default profile propagates the first NaN
+    assert_eq!(hex(b.add(&a)), Ok("7fc00002".to_owned()));
+    assert_eq!(hex(one.sub(&s)), Ok("7fc00003".to_owned()));
+    let bfloat16 = Format { specials: Specials::ieee(), ..Format::new(8, 7, 127) };
+    assert_eq!(hex(nan(0xffd00001).convert_to(bfloat16.clone(), RoundingMode::NearestEven)), Ok("ffd0".to_owned()));
+    assert_eq!(hex(nan(0x7f800001).convert_to(bfloat16.clone(), RoundingMode::NearestEven)), Ok("7fc0".to_owned()));
+}
+
+#[test]
+fn overflow_and_underflow_policies() {
+    let fp8 = Format { specials: Specials { nan: NanEncoding::AllOnes, ..Specials::finite() }, ..Format::new(4, 3, 7) };
+    let (big, inf, tiny) = (Float::from(1000f32), Float::from(f32::NEG_INFINITY), Float::from(1e-10f32));
+
+    // overflow rounds to infinity by default, an error without infinity
+    let mut ctx = FloatContext::default();
+    assert_eq!(hex(ctx.convert(&big, fp8.clone())), Err(error::Error::OutOfRange));
+    assert_eq!(hex(ctx.from_str(Format::ieee_binary32(), &"9".repeat(40))), Ok("7f800000".to_owned()));
+
+    let mut ctx = FloatContext { overflow: OverflowPolicy::Saturate, ..Default::default() };
+    assert_eq!(hex(ctx.convert(&big, fp8.clone())), Ok("7e".to_owned()));
+    assert_eq!(hex(ctx.convert(&inf, fp8.clone())), Ok("fe".to_owned()));
+    assert_eq!(hex(ctx.convert(&Float::from(f32::NAN), fp8.clone())), Ok("7f".to_owned()));
+    assert_eq!(hex(ctx.mul(&Float::from(f32::MAX), &Float::from(2f32))), Ok("7f7fffff".to_owned()));
+    assert_eq!(hex(ctx.div(&Float::from(-1f32), &Float::from(0f32))), Ok("ff800000".to_owned()));
+    assert_eq!(hex(ctx.add(&Float::from(f32::INFINITY), &Float::from(1f32))), Ok("7f800000".to_owned()));
+    assert_eq!(hex(ctx.sqrt(&Float::from(f32::INFINITY))), Ok("7f800000".to_owned()));
+    assert_eq!(ctx.flags, Flags { overflow: true, inexact: true, divide_by_zero: true, ..Default::default() });
+
+    let mut ctx = FloatContext { overflow: OverflowPolicy::Error, ..Default::default() };
+    assert_eq!(hex(ctx.mul(&Float::from(f32::MAX), &Float::from(2f32))), Err(error::Error::OutOfRange));
+    assert_eq!(hex(ctx.add(&Float::from(f32::INFINITY), &Float::from(1f32))), Ok("7f800000".to_owned()));
+    assert!(ctx.flags.overflow);
+
+    // underflow to zero, or to the smallest subnormal
+    let mut ctx = FloatContext::default();
+    assert_eq!(hex(ctx.convert(&tiny, fp8.clone())), Ok("00".to_owned()));
+    assert_eq!(hex(ctx.convert(&-&tiny, fp8.clone())), Ok("80".to_owned()));
+
+    let mut ctx = FloatContext { underflow: UnderflowPolicy::MinSubnormal, ..Default::default() };
+    assert_eq!(hex(ctx.convert(&tiny, fp8.clone())), Ok("01".to_owned()));
+    assert_eq!(hex(ctx.convert(&-&tiny, fp8.clone())), Ok("81".to_owned()));
+    assert_eq!(hex(ctx.mul(&Float::from(1e-30f32), &Float::from(1e-30f32))), Ok("00000001".to_owned()));
+    assert_eq!(hex(ctx.convert(&Float::from(0f32), fp8.clone())), Ok("00".to_owned()));
+    assert_eq!(ctx.flags, Flags { underflow: true, inexact: true, ..Default::default() });
+
+    let mut ctx = FloatContext { underflow: UnderflowPolicy::Error, ..Default::default() };
+    assert_eq!(hex(ctx.from_str(fp8.clone(), "0.0001")), Err(error::Error::OutOfRange));
+    assert_eq!(hex(ctx.from_str(fp8.clone(), "0.002")), Ok("01".to_owned()));
+
+    // tiny results flushed to zero are not affected
+    let mut ctx = FloatContext { underflow: UnderflowPolicy::Error, flush_to_zero: true, ..Default::default() };
+    assert_eq!(hex(ctx.from_str(fp8.clone(), "0.0001")), Ok("00".to_owned()));
+}
@@ -1,5 +1,7 @@
-use float_format::Format;
-use float_format::IeeeBinary;
+use float_format::*;
+
+mod common;
+use common::{bits, hex};
 
 #[test]
 fn ieee_formats() {
@@ -12,4 +14,265 @@ fn ieee_formats() {
         Format::ieee_binary64(),
         Format{ signed: true, exp: 11, mant: 52, excess: 1023, .. },
     ));
-}
\ No newline at end of file
+}
+#[test]
+fn special_values() {
+    let parse = |format: &Format, s: &str| Float::from_str(format.clone(), s).map(|f| f.bits.to_hex_string());
+
+    // NaN only at the all one patterns, no infinities
+    let all_ones = Format {
+        specials: Specials { infinity: false, nan: NanEncoding::AllOnes, ..Specials::finite() },
+        ..Format::new(4, 3, 7)
+    };
+    assert_eq!(parse(&all_ones, "448"), Ok("7e".to_owned()));
+    assert_eq!(parse(&all_ones, "1000"), Err(error::Error::OutOfRange));
+    assert_eq!(format!("{}", bits(&all_ones, "7f")), "NaN");
+    assert_eq!(format!("{}", bits(&all_ones, "ff")), "NaN");
+    assert_eq!(format!("{}", bits(&all_ones, "78")), "256");
+    assert_eq!(bits(&all_ones, "7e").add(&bits(&all_ones, "7e")), Err(error::Error::OutOfRange));
+    assert_eq!(bits(&all_ones, "00").div(&bits(&all_ones, "00")).unwrap().bits.to_hex_string(), "7f");
+
+    // NaN at the negative zero pattern, no negative zero
+    let neg_zero = Format {
+        specials: Specials { infinity: false, nan: NanEncoding::NegativeZero, negative_zero: false, ..Specials::finite() },
+        ..Format::new(4, 3, 8)
+    };
+    assert_eq!(parse(&neg_zero, "-0"), Ok("00".to_owned()));
+    assert_eq!(parse(&neg_zero, "240"), Ok("7f".to_owned()));
+    assert_eq!(format!("{}", bits(&neg_zero, "80")), "NaN");
+    assert_eq!(-bits(&neg_zero, "00"), bits(&neg_zero, "00"));
+    assert_eq!((-bits(&neg_zero, "08")).mul(&bits(&neg_zero, "00")).unwrap(), bits(&neg_zero, "00"));
+    assert_eq!(bits(&neg_zero, "00").div(&bits(&neg_zero, "00")).unwrap().bits.to_hex_string(), "80");
+    assert_eq!(Float::from(f32::NAN).convert_to(neg_zero.clone(), RoundingMode::NearestEven).unwrap().bits.to_hex_string(), "80");
+
+    // finite only, without negative zero
+    let no_neg_zero = Format {
+        specials: Specials { negative_zero: false, ..Specials::finite() },
+        ..Format::new(4, 3, 7)
+    };
+    assert_eq!(bits(&no_neg_zero, "80").value(), Value::Zero { negative: false });
+    assert_eq!(format!("{}", bits(&no_neg_zero, "80")), "0");
+    assert_eq!(Float::from(-0f32).convert_to(no_neg_zero.clone(), RoundingMode::NearestEven).unwrap().bits.to_hex_string(), "00");
+    assert_eq!(Float::from(f32::NAN).convert_to(no_neg_zero.clone(), RoundingMode::NearestEven), Err(error::Error::InvalidOperation));
+
+    // IEEE special values in a custom format
+    let ieee = Format { specials: Specials::ieee(), ..Format::new(5, 2, 15) };
+    assert_eq!(parse(&ieee, "57344"), Ok("7b".to_owned()));
+    assert_eq!(parse(&ieee, "65536"), Ok("7c".to_owned()));
+    assert_eq!(format!("{}", bits(&ieee, "fc")), "-inf");
+    assert_eq!(format!("{}", bits(&ieee, "7e")), "NaN");
+    assert_eq!(format!("{}", bits(&ieee, "7d")), "sNaN");
+}
+
+#[test]
+fn preset_formats() {
+    assert!(matches!(Format::ieee_binary16(), Format { signed: true, exp: 5, mant: 10, excess: 15, .. }));
+    assert!(matches!(Format::bfloat16(), Format { signed: true, exp: 8, mant: 7, excess: 127, .. }));
+    assert!(matches!(Format::tf32(), Format { signed: true, exp: 8, mant: 10, excess: 127, .. }));
+    assert_eq!(Format::tf32().len(), 19);
+
+    // binary16
+    let half = |bits: u16| Float::from_ieee_binary16_bits(bits);
+    assert_eq!(half(0x3c00).to_f32(), 1f32);
+    assert_eq!(half(0x7bff).to_f32(), 65504f32);
+    assert_eq!(half(0x0001).to_f32(), 2f32.powi(-24));
+    assert_eq!(half(0xfc00).to_f32(), -f32::INFINITY);
+    assert!(half(0x7e00).is_nan());
+    assert_eq!(Float::from(1f32).to_ieee_binary16_bits(), 0x3c00);
+    assert_eq!(Float::from(65520f32).to_ieee_binary16_bits(), 0x7c00);
+    assert_eq!(Float::from(-2f32.powi(-25)).to_ieee_binary16_bits(), 0x8000);
+    assert_eq!(Float::from(f32::NAN).to_ieee_binary16_bits(), 0x7e00);
+
+    // bfloat16 is the upper half of binary32
+    for x in [1f32, -2.5f32, f32::MAX, f32::from_bits(0x00010000), -f32::INFINITY] {
+        let bits = x.to_bits() & 0xffff0000;
+        let x = f32::from_bits(bits);
+        assert_eq!(Float::from_bfloat16_bits((bits >> 16) as u16).to_f32(), x);
+        assert_eq!(Float::from(x).to_bfloat16_bits(), (bits >> 16) as u16);
+    }
+    assert_eq!(Float::from(1f32 + 2f32.powi(-8)).to_bfloat16_bits(), 0x3f80);
+    assert_eq!(Float::from(1f32 + 3f32 * 2f32.powi(-8)).to_bfloat16_bits(), 0x3f82);
+
+    // TF32 is the upper 19 bits of binary32
+    assert_eq!(Float::from(1f32).to_tf32_bits(), 0x3f800000 >> 13);
+    assert_eq!(Float::from_tf32_bits(0x7fc00000 >> 13).unwrap().to_f32().to_bits(), 0x7fc00000);
+    assert_eq!(Float::from_tf32_bits(0x3fc00000 >> 13).unwrap().to_f32(), 1.5f32);
+    assert_eq!(Float::from_tf32_bits(1 << 19), Err(error::Error::InsufficientBitsForBitPattern));
+}
+
+#[test]
+fn interchange_formats() {
+    assert_eq!(Format::ieee_interchange(16), Format::ieee_binary16());
+    assert_eq!(Format::ieee_interchange(32), Format::ieee_binary32());
+    assert_eq!(Format::ieee_interchange(64), Format::ieee_binary64());
+    assert!(matches!(Format::ieee_binary128(), Format { signed: true, exp: 15, mant: 112, excess: 16383, .. }));
+    assert!(matches!(Format::ieee_binary256(), Format { signed: true, exp: 19, mant: 236, excess: 262143, .. }));
+    assert!(matches!(Format::ieee_interchange(160), Format { exp: 16, mant: 143, excess: 32767, .. }));
+    assert!(std::panic::catch_unwind(|| Format::ieee_interchange(96)).is_err());
+
+    // binary128
+    let one = 0x3fff_u128 << 112;
+    assert_eq!(Float::from(1f32).to_ieee_binary128_bits(), one);
+    assert_eq!(Float::from_ieee_binary128_bits(one | (1 << 111)).to_f32(), 1.5f32);
+    assert_eq!(Float::from_ieee_binary128_bits(1), Float::from_str(Format::ieee_binary128(), "0").unwrap().next_up().unwrap());
+    assert_eq!(Float::from(-f32::INFINITY).to_ieee_binary128_bits(), 0xffff_u128 << 112);
+    assert_eq!(Float::from(f32::from_bits(1)).to_ieee_binary128_bits(), (0x3fff_u128 - 149) << 112);
+    assert!(Float::from_ieee_binary128_bits(0x7fff_8000_u128 << 96).is_nan());
+
+    // binary256 holds every binary32 exactly
+    let octuple = Format::ieee_binary256();
+    for x in [f32::MAX, f32::from_bits(1), -1.25f32] {
+        let y = Float::from(x).convert_to(octuple.clone(), RoundingMode::NearestEven).unwrap();
+        assert!(y.equals_value(&Float::from(x)));
+        assert!(y.is_normal());
+    }
+}
+
+#[test]
+fn ocp_fp8_formats() {
+    // E4M3 has no infinities and a single NaN pattern per sign
+    let e4m3 = Format::ocp_fp8_e4m3();
+    assert_eq!(format!("{}", bits(&e4m3, "7e")), "448");
+    assert_eq!(format!("{}", bits(&e4m3, "7f")), "NaN");
+    assert_eq!(format!("{}", bits(&e4m3, "ff")), "NaN");
+    assert_eq!(format!("{}", bits(&e4m3, "78")), "256");
+    assert_eq!(format!("{}", bits(&e4m3, "f8")), "-256");
+    assert_eq!(bits(&e4m3, "01").to_f32(), 2f32.powi(-9));
+    assert_eq!(bits(&e4m3, "01").category(), Category::Subnormal);
+    assert_eq!(bits(&e4m3, "7f").category(), Category::Nan);
+    assert_eq!(bits(&e4m3, "7e").category(), Category::Normal);
+    assert_eq!(hex(Float::from_str(e4m3.clone(), "448")), Ok("7e".to_owned()));
+    assert_eq!(hex(Float::from_str(e4m3.clone(), "-0.001953125")), Ok("81".to_owned()));
+    assert_eq!(hex(Float::from_str(e4m3.clone(), "464")), Ok("7e".to_owned()));
+    assert_eq!(hex(Float::from_str(e4m3.clone(), "470")), Err(error::Error::OutOfRange));
+
+    // E5M2 has IEEE special values
+    let e5m2 = Format::ocp_fp8_e5m2();
+    assert_eq!(format!("{}", bits(&e5m2, "7b")), "57344");
+    assert_eq!(format!("{}", bits(&e5m2, "7c")), "inf");
+    assert_eq!(format!("{}", bits(&e5m2, "fc")), "-inf");
+    assert_eq!(format!("{}", bits(&e5m2, "7e")), "NaN");
+    assert_eq!(format!("{}", bits(&e5m2, "7d")), "sNaN");
+    assert_eq!(bits(&e5m2, "01").to_f32(), 2f32.powi(-16));
+    assert_eq!(bits(&e5m2, "7c").category(), Category::Infinite);
+
+    // saturating and non-saturating conversions from binary32
+    let mut saturating = FloatContext { overflow: OverflowPolicy::Saturate, ..Default::default() };
+    let mut non_saturating = FloatContext { overflow: OverflowPolicy::NaN, ..Default::default() };
+
+    let cases = [
+        (1000f32, "7e", "7f", "64", "64"),
+        (-1000f32, "fe", "7f", "e4", "e4"),
+        (f32::INFINITY, "7e", "7f", "7c", "7c"),
+        (-f32::INFINITY, "fe", "7f", "fc", "fc"),
+        (1e6f32, "7e", "7f", "7b", "7c"),
+        (448f32, "7e", "7e", "5f", "5f"),
+        (1.0625f32, "38", "38", "3c", "3c"),
+        (f32::NAN, "7f", "7f", "7e", "7e"),
+    ];
+
+    for (x, e4m3_sat, e4m3_non_sat, e5m2_sat, e5m2_non_sat) in cases {
+        let x = Float::from(x);
+        assert_eq!(hex(saturating.convert(&x, e4m3.clone())), Ok(e4m3_sat.to_owned()), "saturating e4m3 {}", x);
+        assert_eq!(hex(non_saturating.convert(&x, e4m3.clone())), Ok(e4m3_non_sat.to_owned()), "e4m3 {}", x);
+        assert_eq!(hex(saturating.convert(&x, e5m2.clone())), Ok(e5m2_sat.to_owned()), "saturating e5m2 {}", x);
+        assert_eq!(hex(non_saturating.convert(&x, e5m2.clone())), Ok(e5m2_non_sat.to_owned()), "e5m2 {}", x);
+    }
+
+    // the default context has no NaN for overflow
+    assert_eq!(hex(FloatContext::default().convert(&Float::from(1000f32), e4m3.clone())), Err(error::Error::OutOfRange));
+}
+
+#[test]
+fn fnuz_fp8_formats() {
+    let e4m3 = Format::fp8_e4m3fnuz();
+    let e5m2 = Format::fp8_e5m2fnuz();
+
+    // the negative zero pattern is the only NaN, and every other pattern is finite
+    for format in [&e4m3, &e5m2] {
+        assert_eq!(format!("{}", bits(format, "80")), "NaN");
+        assert_eq!(bits(format, "80").category(), Category::Nan);
+        assert_eq!(format!("{}", bits(format, "00")), "0");
+        assert!((1..=255).filter(|b| *b != 0x80).all(|b| bits(format, &format!("{:02x}", b)).is_finite()));
+        assert_eq!(hex(Float::from_str(format.clone(), "-0")), Ok("00".to_owned()));
+        assert_eq!(hex(FloatContext::default().convert(&Float::from(-0f32), format.clone())), Ok("00".to_owned()));
+        assert_eq!(hex(FloatContext::default().convert(&Float::from(f32::NAN), format.clone())), Ok("80".to_owned()));
+        assert_eq!(hex(FloatContext::default().convert(&Float::from(-1e-10f32), format.clone())), Ok("00".to_owned()));
+        assert_eq!(hex(bits(format, "00").neg()), Ok("00".to_owned()));
+    }
+
+    assert_eq!(format!("{}", bits(&e4m3, "7f")), "240");
+    assert_eq!(format!("{}", bits(&e4m3, "ff")), "-240");
+    assert_eq!(format!("{}", bits(&e4m3, "40")), "1");
+    assert_eq!(bits(&e4m3, "01").to_f32(), 2f32.powi(-10));
+    assert_eq!(format!("{}", bits(&e5m2, "7f")), "57344");
+    assert_eq!(format!("{}", bits(&e5m2, "40")), "1");
+    assert_eq!(bits(&e5m2, "01").to_f32(), 2f32.powi(-17));
+
+    // overflow and infinities go to NaN, the largest value, or an error by the overflow policy
+    let mut saturating = FloatContext { overflow: OverflowPolicy::Saturate, ..Default::default() };
+    let mut non_saturating = FloatContext { overflow: OverflowPolicy::NaN, ..Default::default() };
+    assert_eq!(hex(saturating.convert(&Float::from(-f32::INFINITY), e4m3.clone())), Ok("ff".to_owned()));
+    assert_eq!(hex(non_saturating.convert(&Float::from(-f32::INFINITY), e4m3.clone())), Ok("80".to_owned()));
+    assert_eq!(hex(non_saturating.convert(&Float::from(1e6f32), e5m2.clone())), Ok("80".to_owned()));
+    assert_eq!(hex(FloatContext::default().convert(&Float::from(1e6f32), e5m2.clone())), Err(error::Error::OutOfRange));
+}
+
+#[test]
+fn mx_formats() {
+    let values = |format: &Format, max: &str| -> Vec<f32> {
+        Float::range(&bits(format, "0"), &bits(format, max)).unwrap().map(|x| x.to_f32()).collect()
+    };
+
+    // element formats are finite only
+    let e2m1 = Format::ocp_mx_fp4_e2m1();
+    assert_eq!(values(&e2m1, "7"), [0.0, 0.5, 1.0, 1.5, 2.0, 3.0, 4.0, 6.0]);
+    assert_eq!(format!("{}", bits(&e2m1, "f")), "-6");
+
+    let e2m3 = Format::ocp_mx_fp6_e2m3();
+    assert_eq!(bits(&e2m3, "1f").to_f32(), 7.5);
+    assert_eq!(bits(&e2m3, "01").to_f32(), 0.125);
+    assert_eq!(values(&e2m3, "1f").len(), 32);
+
+    let e3m2 = Format::ocp_mx_fp6_e3m2();
+    assert_eq!(bits(&e3m2, "1f").to_f32(), 28.0);
+    assert_eq!(bits(&e3m2, "01").to_f32(), 0.0625);
+    assert_eq!(bits(&e3m2, "3f").to_f32(), -28.0);
+
+    let mut saturating = FloatContext { overflow: OverflowPolicy::Saturate, ..Default::default() };
+    for (format, max) in [(&e2m1, "7"), (&e2m3, "1f"), (&e3m2, "1f")] {
+        assert!((0..1 << format.len()).all(|b| bits(format, &format!("{:x}", b)).is_finite()));
+        assert_eq!(hex(FloatContext::default().convert(&Float::from(100f32), format.clone())), Err(error::Error::OutOfRange));
+        assert_eq!(saturating.convert(&Float::from(f32::INFINITY), format.clone()), Ok(bits(format, max)));
+    }
+
+    // E8M0 is an unsigned power of two, without zero
+    let e8m0 = Format::ocp_mx_e8m0();
+    assert!(matches!(e8m0, Format { signed: false, exp: 8, mant: 0, excess: 127, .. }));
+    assert_eq!(bits(&e8m0, "00").to_f32(), 2f32.powi(-127));
+    assert_eq!(bits(&e8m0, "7f").to_f32(), 1.0);
+    assert_eq!(bits(&e8m0, "fe").to_f32(), 2f32.powi(127));
+    assert_eq!(format!("{}", bits(&e8m0, "ff")), "NaN");
+    assert_eq!(bits(&e8m0, "00").category(), Category::Normal);
+    assert!((0..=0xfe).all(|b| bits(&e8m0, &format!("{:02x}", b)).to_f32() == 2f32.powi(b - 127)));
+
+    assert_eq!(hex(Float::from_str(e8m0.clone(), "0.25")), Ok("7d".to_owned()));
+    assert_eq!(hex(Float::from_str(e8m0.clone(), "3")), Ok("80".to_owned()));
+    assert_eq!(hex(Float::from_str(e8m0.clone(), "0")), Err(error::Error::OutOfRange));
+    assert_eq!(hex(Float::from_str(e8m0.clone(), "-1")), Err(error::Error::NegativeSign));
+    assert_eq!(hex(FloatContext::default().convert(&Float::from(f32::NAN), e8m0.clone())), Ok("ff".to_owned()));
+    assert_eq!(hex(FloatContext::default().convert(&Float::from(f32::from_bits(0x00300000)), e8m0.clone())), Ok("00".to_owned()));
+    assert_eq!(hex(FloatContext::default().convert(&Float::from(f32::from_bits(0x00200000)), e8m0.clone())), Ok("00".to_owned()));
+    assert_eq!(hex(FloatContext::default().convert(&Float::from(f32::from_bits(1 << 9)), e8m0.clone())), Err(error::Error::OutOfRange));
+
+    let mut min = FloatContext { underflow: UnderflowPolicy::MinSubnormal, ..Default::default() };
+    assert_eq!(hex(min.from_str(e8m0.clone(), "0")), Ok("00".to_owned()));
+    assert_eq!(min.flags, Flags { underflow: true, inexact: true, ..Default::default() });
+    assert_eq!(hex(min.convert(&Float::from(f32::from_bits(1 << 9)), e8m0.clone())), Ok("00".to_owned()));
+
+    assert_eq!(bits(&e8m0, "00").next_down(), Err(error::Error::OutOfRange));
+    assert_eq!(hex(bits(&e8m0, "00").next_up()), Ok("01".to_owned()));
+    assert_eq!(bits(&e8m0, "fe").next_up(), Err(error::Error::OutOfRange));
+    assert_eq!(hex(bits(&e8m0, "80").ulp()), Ok("80".to_owned()));
+    assert_eq!(Float::range(&bits(&e8m0, "00"), &bits(&e8m0, "fe")).unwrap().count(), 255);
+}
@@ -0,0 +1,76 @@
+use float_format::*;
+
+mod common;
+use common::{bits, hex};
+
+#[test]
+fn next_like_f32() {
+    let values = [
+        -f32::INFINITY, -f32::MAX, -1f32, -f32::MIN_POSITIVE, -f32::from_bits(1), -0f32, 0f32, f32::from_bits(1),
+        f32::from_bits(0x007fffff), f32::MIN_POSITIVE, 1f32, 1.5f32, f32::MAX, f32::INFINITY,
+    ];
+
+    for x in values {
+        let float = Float::from(x);
+        assert_eq!(float.next_up().unwrap().to_f32().to_bits(), x.next_up().to_bits(), "next_up {}", x);
+        assert_eq!(float.next_down().unwrap().to_f32().to_bits(), x.next_down().to_bits(), "next_down {}", x);
+    }
+
+    assert!(Float::from(f32::NAN).next_up().unwrap().is_nan());
+
+    assert_eq!(Float::from(1f32).ulp().unwrap().to_f32(), f32::EPSILON);
+    assert_eq!(Float::from(-1.5f32).ulp().unwrap().to_f32(), f32::EPSILON);
+    assert_eq!(Float::from(0f32).ulp().unwrap().to_f32(), f32::from_bits(1));
+    assert_eq!(Float::from(f32::MIN_POSITIVE).ulp().unwrap().to_f32(), f32::from_bits(1));
+    assert_eq!(Float::from(f32::MAX).ulp().unwrap().to_f32(), 2f32.powi(104));
+    assert_eq!(Float::from(f32::INFINITY).ulp().unwrap().to_f32(), f32::INFINITY);
+}
+
+#[test]
+fn next_custom_formats() {
+    // no infinity, the largest finite value is all ones
+    let fp8 = Format::new(4, 3, 7);
+    let max = Float::from_str(fp8.clone(), "480").unwrap();
+    assert_eq!(max.bits.to_hex_string(), "7f");
+    assert_eq!(max.next_up(), Err(error::Error::OutOfRange));
+    assert_eq!(max.next_down().unwrap().bits.to_hex_string(), "7e");
+
+    // no negative zero, the zero crossing goes through the positive zero
+    let format = Format { specials: Specials { negative_zero: false, ..Specials::finite() }, ..fp8.clone() };
+    assert_eq!(hex(bits(&format, "81").next_up()), Ok("00".to_owned()));
+    assert_eq!(hex(bits(&format, "80").next_up()), Ok("01".to_owned()));
+    assert_eq!(hex(bits(&format, "00").next_down()), Ok("81".to_owned()));
+
+    // unsigned formats have nothing below zero
+    let unsigned = Format::new_unsigned(4, 4, 7);
+    let zero = Float::from_str(unsigned.clone(), "0").unwrap();
+    assert_eq!(zero.next_down(), Err(error::Error::NegativeSign));
+    assert_eq!(zero.next_up().unwrap().bits.to_hex_string(), "01");
+}
+
+#[test]
+fn range_of_values() {
+    let fp8 = Format::new(4, 3, 7);
+    let max = Float::from_str(fp8.clone(), "480").unwrap();
+    let min = Float::from_str(fp8.clone(), "-480").unwrap();
+
+    // every value once, with a single zero
+    let values: Vec<_> = Float::range(&min, &max).unwrap().collect();
+    assert_eq!(values.len(), 255);
+    assert!(values.windows(2).all(|w| w[0].cmp_value(&w[1]) == Some(std::cmp::Ordering::Less)));
+    assert_eq!(values.first(), Some(&min));
+    assert_eq!(values.last(), Some(&max));
+
+    // special encodings are skipped and infinities are reached
+    let subnormal = Float::from(f32::from_bits(0x007ffffe));
+    let values: Vec<_> = Float::range(&subnormal, &Float::from(f32::MIN_POSITIVE)).unwrap().map(|x| x.to_f32()).collect();
+    assert_eq!(values, [f32::from_bits(0x007ffffe), f32::from_bits(0x007fffff), f32::MIN_POSITIVE]);
+
+    let values: Vec<_> = Float::range(&Float::from(f32::MAX), &Float::from(f32::INFINITY)).unwrap().map(|x| x.to_f32()).collect();
+    assert_eq!(values, [f32::MAX, f32::INFINITY]);
+
+    assert_eq!(Float::range(&Float::from(1f32), &Float::from(0f32)).unwrap().count(), 0);
+    assert!(Float::range(&Float::from(f32::NAN), &Float::from(0f32)).is_err());
+    let other = Float::from_str(Format::new(4, 3, 7), "0").unwrap();
+    assert_eq!(Float::range(&Float::from(0f32), &other).err(), Some(error::Error::MismatchedFormat));
+}
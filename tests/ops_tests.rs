@@ -0,0 +1,192 @@
+use float_format::*;
+
+/// Generate pseudo random `f32` values with exponents well within the normal range.
+fn samples() -> Vec<f32> {
+    let mut state = 0x2545f491u32;
+
+    (0..200)
+        .map(|_| {
+            state = state.wrapping_mul(1664525).wrapping_add(1013904223);
+            let exp = 100 + (state >> 24) % 56;
+            f32::from_bits((state & 0x807fffff) | (exp << 23))
+        })
+        .collect()
+}
+
+#[test]
+fn arithmetic_matches_f32() {
+    let samples = samples();
+
+    for pair in samples.windows(2) {
+        let (a, b) = (pair[0], pair[1]);
+        let (x, y) = (Float::from(a), Float::from(b));
+
+        assert_eq!(x.add(&y).unwrap(), Float::from(a + b), "{} + {}", a, b);
+        assert_eq!(x.sub(&y).unwrap(), Float::from(a - b), "{} - {}", a, b);
+        assert_eq!(x.mul(&y).unwrap(), Float::from(a * b), "{} * {}", a, b);
+        assert_eq!(x.div(&y).unwrap(), Float::from(a / b), "{} / {}", a, b);
+    }
+
+    #[cfg(target_pointer_width = "64")]
+    {
+        let (a, b) = (0.1f64, 0.2f64);
+        assert_eq!(Float::from(a) + Float::from(b), Float::from(a + b));
+        assert_eq!(&Float::from(a) / &Float::from(3f64), Float::from(a / 3f64));
+    }
+}
+
+#[test]
+fn subnormal_arithmetic() {
+    let samples = samples();
+
+    for pair in samples.windows(2) {
+        let (a, b) = (pair[0] * 2f32.powi(-120), pair[1] * 2f32.powi(-30));
+        let (x, y) = (Float::from(a), Float::from(b));
+
+        assert_eq!(x.add(&x).unwrap(), Float::from(a + a), "{} + {}", a, a);
+        assert_eq!(x.sub(&y).unwrap(), Float::from(a - b), "{} - {}", a, b);
+        assert_eq!(x.mul(&y).unwrap(), Float::from(a * b), "{} * {}", a, b);
+        assert_eq!(x.div(&y).unwrap(), Float::from(a / b), "{} / {}", a, b);
+        assert_eq!(Float::from(a.abs()).sqrt().unwrap(), Float::from(a.abs().sqrt()), "sqrt {}", a);
+    }
+}
+
+#[test]
+fn operators_and_special_values() {
+    let one = Float::from(1f32);
+    let zero = Float::from(0f32);
+
+    assert_eq!(&one - &one, zero);
+    assert_eq!(-&zero - zero.clone(), Float::from(-0f32));
+    assert_eq!(-one.clone(), Float::from(-1f32));
+
+    assert_eq!(format!("{}", &one / &zero), "inf");
+    assert_eq!(format!("{}", -&one / zero.clone()), "-inf");
+    assert_eq!(format!("{}", &zero / &zero), "NaN");
+    assert_eq!(format!("{}", (&one / &zero) - (&one / &zero)), "NaN");
+    assert_eq!(format!("{}", Float::from(f32::MAX) * Float::from(2f32)), "inf");
+}
+
+#[test]
+fn errors() {
+    let format = Format::new(4, 3, 7);
+    let max = Float::from_bits(format.clone(), BitPattern::from_bin_str("01111111")).unwrap();
+    let zero = Float::from_bits(format.clone(), BitPattern::from_bin_str("00000000")).unwrap();
+
+    assert_eq!(max.add(&max), Err(error::Error::OutOfRange));
+    assert_eq!(max.div(&zero), Err(error::Error::DivisionByZero));
+    assert_eq!(zero.div(&zero), Err(error::Error::InvalidOperation));
+    assert_eq!(max.add(&Float::from(1f32)), Err(error::Error::MismatchedFormat));
+
+    let format = Format::new_unsigned(4, 3, 7);
+    let one = Float::from_bits(format.clone(), BitPattern::from_bin_str("0111000")).unwrap();
+    let two = Float::from_bits(format.clone(), BitPattern::from_bin_str("1000000")).unwrap();
+
+    assert_eq!(two.sub(&one), Ok(one.clone()));
+    assert_eq!(one.sub(&two), Err(error::Error::NegativeSign));
+}
+
+#[test]
+fn wide_format() {
+    let format = Format::new_ieee_excess(16, 128);
+    let one = Float::from_bits(
+        format.clone(),
+        BitPattern::from_bin_str(&("00".to_owned() + &"1".repeat(15) + &"0".repeat(128))),
+    ).unwrap();
+    let three = &(&one + &one) + &one;
+    let third = &one / &three;
+
+    // 1/3 has the alternating pattern 0101... in the mantissa
+    let mant = third.to_comps().mant.to_bin_string();
+    assert_eq!(&mant[..4], "0101");
+    assert_eq!(&mant[124..], "0101");
+
+    assert_eq!(&third * &three, one);
+}
+
+#[test]
+fn fused_multiply_add() {
+    let samples = samples();
+
+    for triple in samples.windows(3) {
+        let (a, b, c) = (triple[0], triple[1], triple[2]);
+        let (x, y, z) = (Float::from(a), Float::from(b), Float::from(c));
+
+        assert_eq!(x.mul_add(&y, &z).unwrap(), Float::from(a.mul_add(b, c)), "{} * {} + {}", a, b, c);
+    }
+
+    // the product rounds to 1 when computed separately
+    let eps = f32::EPSILON;
+    let (a, b, c) = (Float::from(1f32 + eps), Float::from(1f32 - eps), Float::from(-1f32));
+    assert_eq!(a.mul_add(&b, &c).unwrap(), Float::from(-eps * eps));
+    assert_eq!(&(&a * &b) + &c, Float::from(0f32));
+
+    let (one, zero, inf) = (Float::from(1f32), Float::from(0f32), Float::from(f32::INFINITY));
+    assert_eq!(one.mul_add(&one, &-&one).unwrap(), zero);
+    assert_eq!((-&zero).mul_add(&one, &-&zero).unwrap(), -&zero);
+    assert_eq!(format!("{}", inf.mul_add(&zero, &one).unwrap()), "NaN");
+    assert_eq!(format!("{}", inf.mul_add(&one, &-&inf).unwrap()), "NaN");
+    assert_eq!(one.mul_add(&one, &inf).unwrap(), inf);
+}
+
+#[test]
+fn square_roots() {
+    for a in samples().into_iter().map(f32::abs) {
+        let x = Float::from(a);
+
+        assert_eq!(x.sqrt().unwrap(), Float::from(a.sqrt()), "sqrt {}", a);
+        assert_eq!(x.rsqrt().unwrap(), Float::from((1f64 / (a as f64).sqrt()) as f32), "rsqrt {}", a);
+    }
+
+    let format = Format::new(16, 128, 32767);
+    let nine = Float::from_str(format.clone(), "9").unwrap();
+    let three = Float::from_str(format.clone(), "3").unwrap();
+    assert_eq!(nine.sqrt().unwrap(), three);
+
+    // the square root of a rounded square gives back the original number
+    let third = &Float::from_str(format.clone(), "1").unwrap() / &three;
+    assert_eq!((&third * &third).sqrt().unwrap(), third);
+
+    assert_eq!(nine.rsqrt().unwrap(), &Float::from_str(format.clone(), "1").unwrap() / &three);
+    assert_eq!(Float::from_str(format.clone(), "-9").unwrap().sqrt(), Err(error::Error::InvalidOperation));
+
+    let (zero, inf) = (Float::from(0f32), Float::from(f32::INFINITY));
+    assert_eq!((-&zero).sqrt().unwrap(), -&zero);
+    assert_eq!((-&zero).rsqrt().unwrap(), -&inf);
+    assert_eq!(inf.rsqrt().unwrap(), zero);
+    assert_eq!(format!("{}", Float::from(-4f32).sqrt().unwrap()), "NaN");
+}
+
+#[test]
+fn integral_and_remainder() {
+    let samples = samples();
+
+    for a in samples.iter().flat_map(|a| [*a, -a, a / 64f32.powi(3)]) {
+        let x = Float::from(a);
+
+        assert_eq!(x.floor().unwrap(), Float::from(a.floor()), "floor {}", a);
+        assert_eq!(x.ceil().unwrap(), Float::from(a.ceil()), "ceil {}", a);
+        assert_eq!(x.trunc().unwrap(), Float::from(a.trunc()), "trunc {}", a);
+        assert_eq!(x.round().unwrap(), Float::from(a.round()), "round {}", a);
+        assert_eq!(x.round_ties_even().unwrap(), Float::from(a.round_ties_even()), "round_ties_even {}", a);
+    }
+
+    for pair in samples.windows(2) {
+        let (a, b) = (pair[0], pair[1]);
+        assert_eq!(Float::from(a).fmod(&Float::from(b)).unwrap(), Float::from(a % b), "{} fmod {}", a, b);
+    }
+
+    let remainder = |a: f32, b: f32| Float::from(a).remainder(&Float::from(b)).unwrap();
+    assert_eq!(remainder(5f32, 2f32), Float::from(1f32));
+    assert_eq!(remainder(7f32, 2f32), Float::from(-1f32));
+    assert_eq!(remainder(-7f32, 2f32), Float::from(1f32));
+    assert_eq!(remainder(-4f32, 2f32), Float::from(-0f32));
+    assert_eq!(remainder(3f32, f32::INFINITY), Float::from(3f32));
+    assert_eq!(format!("{}", remainder(3f32, 0f32)), "NaN");
+    assert_eq!(Float::from(-0.5f32).ceil().unwrap(), Float::from(-0f32));
+
+    let format = Format::new(16, 128, 32767);
+    let ten_thirds = &Float::from_str(format.clone(), "10").unwrap() / &Float::from_str(format.clone(), "3").unwrap();
+    assert_eq!(ten_thirds.floor().unwrap(), Float::from_str(format.clone(), "3").unwrap());
+    assert_eq!(ten_thirds.ceil().unwrap(), Float::from_str(format.clone(), "4").unwrap());
+}
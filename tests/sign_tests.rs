@@ -0,0 +1,63 @@
+use float_format::*;
+
+mod common;
+use common::{bits, hex};
+
+#[test]
+fn sign_like_f32() {
+    let values = [
+        -f32::INFINITY, -1.5f32, -f32::from_bits(1), -0f32, 0f32, f32::from_bits(1), 1.5f32, f32::INFINITY,
+        f32::from_bits(0x7fc00001), f32::from_bits(0xff800001),
+    ];
+
+    for x in values {
+        let float = Float::from(x);
+        assert_eq!(float.neg().unwrap(), Float::from(-x), "neg {:x}", x.to_bits());
+        assert_eq!(-&float, Float::from(-x), "neg {:x}", x.to_bits());
+        assert_eq!(float.abs().unwrap(), Float::from(x.abs()), "abs {:x}", x.to_bits());
+
+        for y in values {
+            let sign = Float::from(y);
+            assert_eq!(float.copysign(&sign).unwrap(), Float::from(x.copysign(y)), "copysign {:x} {:x}", x.to_bits(), y.to_bits());
+        }
+
+        match x.is_nan() {
+            true => assert_eq!(float.signum().unwrap(), float),
+            false => assert_eq!(float.signum().unwrap().to_f32(), x.signum(), "signum {}", x),
+        }
+    }
+
+    // the sign may be taken from another format
+    let sign = Float::from_str(Format::new(4, 3, 7), "-0").unwrap();
+    assert_eq!(Float::from(2f32).copysign(&sign).unwrap().to_f32(), -2f32);
+}
+
+#[test]
+fn sign_custom_formats() {
+    // unsigned formats have no sign bit
+    let unsigned = Float::from_str(Format::new_unsigned(4, 4, 7), "1.5").unwrap();
+    assert_eq!(unsigned.neg(), Err(error::Error::NegativeSign));
+    assert_eq!(unsigned.abs().unwrap(), unsigned);
+    assert_eq!(unsigned.copysign(&Float::from(-1f32)), Err(error::Error::NegativeSign));
+    assert_eq!(unsigned.copysign(&Float::from(1f32)).unwrap(), unsigned);
+    assert_eq!(unsigned.signum().unwrap().to_f32(), 1f32);
+
+    // no negative zero, the zero stays positive
+    let format = Format { specials: Specials { negative_zero: false, ..Specials::finite() }, ..Format::new(4, 3, 7) };
+    assert_eq!(hex(bits(&format, "00").neg()), Ok("00".to_owned()));
+    assert_eq!(hex(bits(&format, "80").abs()), Ok("00".to_owned()));
+    assert_eq!(hex(bits(&format, "01").neg()), Ok("81".to_owned()));
+
+    // the NaN encoded by the sign bit is kept
+    let format = Format {
+        specials: Specials { nan: NanEncoding::NegativeZero, negative_zero: false, ..Specials::finite() },
+        ..Format::new(4, 3, 7)
+    };
+    assert!(bits(&format, "80").abs().unwrap().is_nan());
+    assert!(bits(&format, "80").neg().unwrap().is_nan());
+    assert_eq!(hex(bits(&format, "00").neg()), Ok("00".to_owned()));
+
+    // one must be representable for the sign
+    let tiny = Float::from_str(Format::new(2, 3, 8), "0.01").unwrap();
+    assert_eq!(tiny.signum(), Err(error::Error::OutOfRange));
+}
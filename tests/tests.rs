@@ -57,6 +57,7 @@ fn radices() {
 }
 
 #[test]
+#[allow(clippy::excessive_precision)]
 fn parse_and_display() {
     assert_eq!(
         format!("{:.15}", Float::from_str(Format::ieee_binary32(), "0.000000000000123456789").unwrap()),
@@ -92,7 +93,113 @@ fn extreme_values() {
     );
 
     assert_eq!(
-        format!("{}", Float::from_str(Format::new(16, 128, 32767), "0.00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001").unwrap()),
+        format!("{}", Float::from_str_with_rounding(Format::new(16, 128, 32767), "0.00000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000001", RoundingMode::TowardZero).unwrap()),
         format!("{}", "0.0000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000099999999"),
     );
-}
\ No newline at end of file
+}
+#[test]
+fn parse_rounding_modes() {
+    for s in ["0.5", "1.5", "0.1", "3.14159265358979", "16777217", "123456789.123456789", "0.000001"] {
+        assert_eq!(Float::from_str(Format::ieee_binary32(), s).unwrap(), Float::from(s.parse::<f32>().unwrap()), "{}", s);
+    }
+
+    let parse = |s: &str, mode: RoundingMode| {
+        Float::from_str_with_rounding(Format::ieee_binary32(), s, mode).unwrap().bits.to_hex_string()
+    };
+
+    assert_eq!(parse("0.1", RoundingMode::NearestEven), "3dcccccd");
+    assert_eq!(parse("0.1", RoundingMode::TowardZero), "3dcccccc");
+    assert_eq!(parse("0.1", RoundingMode::TowardPositive), "3dcccccd");
+    assert_eq!(parse("0.1", RoundingMode::TowardNegative), "3dcccccc");
+    assert_eq!(parse("-0.1", RoundingMode::TowardNegative), "bdcccccd");
+    assert_eq!(parse("-0.1", RoundingMode::ToOdd), "bdcccccd");
+
+    assert_eq!(parse("16777217", RoundingMode::NearestEven), "4b800000");
+    assert_eq!(parse("16777217", RoundingMode::NearestAway), "4b800001");
+    assert_eq!(parse("16777217", RoundingMode::ToOdd), "4b800001");
+    assert_eq!(parse("16777216", RoundingMode::ToOdd), "4b800000");
+
+    assert_eq!(parse("1000000000000000000000000000000000000000", RoundingMode::NearestEven), "7f800000");
+    assert_eq!(parse("1000000000000000000000000000000000000000", RoundingMode::TowardZero), "7f7fffff");
+
+    assert_eq!(
+        Float::from_str(Format::new(4, 3, 7), "1000"),
+        Err(error::Error::OutOfRange),
+    );
+    assert_eq!(
+        Float::from_str_with_rounding(Format::new(4, 3, 7), "1000", RoundingMode::TowardZero).unwrap().bits.to_bin_string(),
+        "01111111",
+    );
+}
+
+#[test]
+fn convert_formats() {
+    let bfloat16 = Format {
+        specials: Specials::ieee(),
+        ..Format::new(8, 7, 127)
+    };
+    let fp8 = Format::new(4, 3, 7);
+
+    let convert = |f: &Float, format: &Format, mode: RoundingMode| {
+        f.convert_to(format.clone(), mode).unwrap().bits.to_bin_string()
+    };
+
+    let x = Float::from(1f32 + 2f32.powi(-7));
+    assert_eq!(convert(&x, &bfloat16, RoundingMode::NearestEven), "0011111110000001");
+    assert_eq!(convert(&x, &fp8, RoundingMode::NearestEven), "00111000");
+    assert_eq!(convert(&x, &fp8, RoundingMode::TowardPositive), "00111001");
+
+    let x = Float::from(-1f32 - 2f32.powi(-8));
+    assert_eq!(convert(&x, &bfloat16, RoundingMode::NearestEven), "1011111110000000");
+    assert_eq!(convert(&x, &bfloat16, RoundingMode::NearestAway), "1011111110000001");
+
+    let y = x.convert_to(bfloat16.clone(), RoundingMode::TowardNegative).unwrap();
+    assert_eq!(convert(&y, &Format::ieee_binary32(), RoundingMode::NearestEven), "10111111100000010000000000000000");
+    assert_eq!(convert(&y, &Format::new(8, 7, 100), RoundingMode::NearestEven), "1011001000000001");
+
+    assert_eq!(convert(&Float::from(f32::INFINITY), &bfloat16, RoundingMode::NearestEven), "0111111110000000");
+    assert_eq!(format!("{}", Float::from(f32::NAN).convert_to(bfloat16.clone(), RoundingMode::NearestEven).unwrap()), "NaN");
+    assert_eq!(convert(&Float::from(f32::MAX), &bfloat16, RoundingMode::NearestEven), "0111111110000000");
+    assert_eq!(convert(&Float::from(f32::MAX), &fp8, RoundingMode::TowardZero), "01111111");
+    assert_eq!(Float::from(f32::MAX).convert_to(fp8.clone(), RoundingMode::NearestEven), Err(error::Error::OutOfRange));
+    assert_eq!(Float::from(f32::NAN).convert_to(fp8.clone(), RoundingMode::NearestEven), Err(error::Error::InvalidOperation));
+
+    #[cfg(target_pointer_width = "64")]
+    for x in [0.1f64, 1.0 / 3.0, -123456.789, 6.02214076e23] {
+        assert_eq!(
+            Float::from(x).convert_to(Format::ieee_binary32(), RoundingMode::NearestEven).unwrap(),
+            Float::from(x as f32),
+        );
+    }
+}
+
+#[test]
+fn subnormal_values() {
+    let tiny = [f32::from_bits(1), f32::from_bits(0x7fffff), f32::MIN_POSITIVE / 3f32, -1.5e-40f32, 1e-45f32];
+
+    for x in tiny {
+        let float = Float::from(x);
+        assert_eq!(float.to_f32(), x);
+        assert_eq!(float.to_f64(), x as f64);
+        assert_eq!(format!("{:.60}", float), format!("{:.60}", x));
+        assert_eq!(Float::from_str(Format::ieee_binary32(), &format!("{:.160}", x)).unwrap(), float);
+    }
+
+    #[cfg(target_pointer_width = "64")]
+    for x in [f64::from_bits(1), f64::MIN_POSITIVE / 7f64, -2.5e-310f64] {
+        assert_eq!(Float::from(x).to_f64(), x);
+        assert_eq!(Float::from(x).convert_to(Format::ieee_binary32(), RoundingMode::NearestEven).unwrap(), Float::from(0f32.copysign(x as f32)));
+    }
+
+    // the halfway point to the smallest subnormal rounds to even, i.e. zero
+    let parse = |x: f64| Float::from_str(Format::ieee_binary32(), &format!("{:.160}", x)).unwrap();
+    assert_eq!(parse(2f64.powi(-150)), Float::from(0f32));
+    assert_eq!(parse(2f64.powi(-150) * 1.001), Float::from(f32::from_bits(1)));
+    assert_eq!(parse(2f64.powi(-149) * 1.5), Float::from(f32::from_bits(2)));
+
+    // without subnormals, the all zero exponent has an implicit leading 1
+    let format = Format { subnormal: false, ..Format::ieee_binary32() };
+    let float = Float::from_bits(format.clone(), BitPattern::from_value(1u32)).unwrap();
+    assert_eq!(float.to_f32(), 2f32.powi(-127) * (1f32 + 2f32.powi(-23)));
+    assert_eq!(Float::from_str(format.clone(), &format!("{:.160}", 2f64.powi(-128))).unwrap().bits.to_hex_string(), "00000000");
+}
@@ -0,0 +1,90 @@
+use float_format::*;
+use fraction::{BigFraction, BigUint};
+
+mod common;
+use common::{bits, hex};
+
+#[test]
+fn decode_values() {
+    let finite = |numer: i64, denom: u64| {
+        let value = BigFraction::new(numer.unsigned_abs(), denom);
+        Value::Finite(if numer < 0 { -value } else { value })
+    };
+
+    assert_eq!(Float::from(1.5f32).value(), finite(3, 2));
+    assert_eq!(Float::from(-0.375f32).value(), finite(-3, 8));
+    assert_eq!(Float::from(f32::from_bits(1)).value(), Value::Finite(BigFraction::new(BigUint::from(1u32), BigUint::from(1u32) << 149)));
+    assert_eq!(Float::from(0f32).value(), Value::Zero { negative: false });
+    assert_eq!(Float::from(-0f32).value(), Value::Zero { negative: true });
+    assert_eq!(Float::from(f32::NEG_INFINITY).value(), Value::Infinity { negative: true });
+
+    let nan = |bits: u32| Float::from(f32::from_bits(bits)).value();
+    assert_eq!(nan(0x7fc00000), Value::NaN { quiet: true, payload: 0u32.into() });
+    assert_eq!(nan(0xffc00005), Value::NaN { quiet: true, payload: 5u32.into() });
+    assert_eq!(nan(0x7f800001), Value::NaN { quiet: false, payload: 1u32.into() });
+
+    // every bit pattern is finite without the IEEE interpretation
+    let format = Format::new(4, 3, 7);
+    let decode = |bits: &str| Float::from_bits(format.clone(), BitPattern::from_bin_str(bits)).unwrap().value();
+    assert_eq!(decode("01111111"), finite(480, 1));
+    assert_eq!(decode("11111000"), finite(-256, 1));
+    assert_eq!(decode("00000001"), finite(1, 512));
+    assert_eq!(decode("10000000"), Value::Zero { negative: true });
+    assert_eq!(format.decode(&Float::from_str(format.clone(), "1.125").unwrap().to_comps()), finite(9, 8));
+}
+
+#[test]
+fn nan_payloads() {
+    let binary32 = Format::ieee_binary32();
+
+    assert_eq!(Float::from(f32::from_bits(0x7fc00123)).nan_payload(), Some(0x123u32.into()));
+    assert_eq!(Float::from(f32::from_bits(0xff800001)).nan_payload(), Some(1u32.into()));
+    assert_eq!(Float::from(f32::INFINITY).nan_payload(), None);
+
+    assert_eq!(hex(Float::new_nan(binary32.clone(), true, 0x123u32.into())), Ok("7fc00123".to_owned()));
+    assert_eq!(hex(Float::new_nan(binary32.clone(), false, 5u32.into())), Ok("7f800005".to_owned()));
+    assert_eq!(hex(Float::new_nan(binary32.clone(), false, 0u32.into())), Err(error::Error::UnrepresentableNaN));
+    assert_eq!(hex(Float::new_nan(binary32.clone(), true, (1u32 << 22).into())), Err(error::Error::InsufficientMantissaBits));
+    assert_eq!(hex(Float::new_nan(Format::new(4, 3, 7), true, 0u32.into())), Err(error::Error::UnrepresentableNaN));
+
+    #[cfg(target_pointer_width = "64")]
+    {
+        let boxed = Float::new_nan(Format::ieee_binary64(), true, 0xdeadbeefu32.into()).unwrap();
+        assert_eq!(boxed.to_f64_raw().to_bits(), 0x7ff80000deadbeef);
+        assert_eq!(Float::from(boxed.to_f64_raw()).nan_payload(), Some(0xdeadbeefu32.into()));
+    }
+
+    // canonicalization quiets signaling NaN, keeping the sign and payload
+    let mut ctx = FloatContext::default();
+    assert_eq!(hex(ctx.canonicalize(&Float::from(f32::from_bits(0xff800005)))), Ok("ffc00005".to_owned()));
+    assert!(ctx.flags.invalid);
+
+    let mut ctx = FloatContext::default();
+    assert_eq!(hex(ctx.canonicalize(&Float::from(f32::from_bits(0x7fc00005)))), Ok("7fc00005".to_owned()));
+    assert_eq!(hex(ctx.canonicalize(&Float::from(-1.5f32))), Ok("bfc00000".to_owned()));
+    assert!(ctx.flags.is_empty());
+
+    let no_neg_zero = Format { specials: Specials { negative_zero: false, ..Specials::finite() }, ..Format::new(4, 3, 7) };
+    let zero = bits(&no_neg_zero, "80");
+    assert_eq!(hex(zero.canonicalize()), Ok("00".to_owned()));
+
+    // legacy NaN has the quiet bit clear
+    let legacy = Format { specials: Specials { quiet_bit: QuietBit::Legacy, ..Specials::ieee() }, ..Format::ieee_binary32() };
+    assert_eq!(bits(&legacy, "7fc00000").value(), Value::NaN { quiet: false, payload: 0u32.into() });
+    assert_eq!(bits(&legacy, "7fbfffff").value(), Value::NaN { quiet: true, payload: 0x3fffffu32.into() });
+    assert_eq!(format!("{}", bits(&legacy, "7fc00001")), "sNaN");
+    assert_eq!(hex(Float::new_nan(legacy.clone(), true, 0u32.into())), Err(error::Error::UnrepresentableNaN));
+    assert_eq!(hex(Float::new_nan(legacy.clone(), false, 0u32.into())), Ok("7fc00000".to_owned()));
+    assert_eq!(hex(bits(&legacy, "00000000").div(&bits(&legacy, "00000000"))), Ok("7fbfffff".to_owned()));
+    assert_eq!(hex(bits(&legacy, "7fc00000").canonicalize()), Ok("7fbfffff".to_owned()));
+    assert_eq!(hex(bits(&legacy, "7fc00005").canonicalize()), Ok("7f800005".to_owned()));
+
+    // formats with a single NaN pattern have no payload
+    let fnuz = Format {
+        specials: Specials { infinity: false, nan: NanEncoding::NegativeZero, negative_zero: false, ..Specials::finite() },
+        ..Format::new(4, 3, 8)
+    };
+    assert_eq!(hex(Float::new_nan(fnuz.clone(), true, 0u32.into())), Ok("80".to_owned()));
+    assert_eq!(hex(Float::new_nan(fnuz.clone(), false, 0u32.into())), Err(error::Error::UnrepresentableNaN));
+    assert_eq!(hex(Float::new_nan(fnuz.clone(), true, 1u32.into())), Err(error::Error::UnrepresentableNaN));
+}